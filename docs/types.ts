@@ -14,7 +14,8 @@ import type { BarrelLoaderOptions } from '@apec1/barrel-loader';
  */
 
 /**
- * @property {boolean} [optimize] - Enables optimization of barrel files. Default: true
+ * @property {boolean} [optimize] - Preset that enables resolution, namespace expansion,
+ *   conflict handling, dedup and sorting. Explicit options override it. Default: false
  * @example
  * ```typescript
  * const options: BarrelLoaderOptions = {
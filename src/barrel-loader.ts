@@ -1,6 +1,9 @@
 import * as fs from 'node:fs';
 import type { BarrelLoaderOptions, LoaderContext } from './barrel-loader.types';
 import { getDedupedExports } from './ts-utils/dedupe';
+import { reportDependencies, trackDependencies } from './ts-utils/dependencies';
+import { nativeAddon } from './ts-utils/native-addon';
+import { createNativeFileSystem } from './ts-utils/native-file-system';
 import { reconstructSource } from './ts-utils/reconstruct';
 import { resolveBarrelExportsRecursive } from './ts-utils/resolve-barrel';
 import { sortExports } from './ts-utils/sort';
@@ -14,13 +17,78 @@ function logVerbose(verbose: boolean, message: string, details?: Record<string,
   console.log(`[barrel-loader] ${message}`);
 }
 
+/**
+ * Addon options with the loader's defaults: re-export chains are flattened unless
+ * `resolveBarrelFiles` is false, and exports are sorted unless `sort` is false
+ */
+function nativeOptions(options: BarrelLoaderOptions): BarrelLoaderOptions {
+  return {
+    ...options,
+    resolveBarrelExports: options.resolveBarrelExports ?? options.resolveBarrelFiles ?? true,
+    sort: options.sort ?? true,
+  };
+}
+
+/**
+ * Process the barrel natively, reporting the files, directories and missing paths
+ * the addon read or probed; `null` when the addon does not provide it
+ */
+function processNatively(
+  context: LoaderContext<BarrelLoaderOptions>,
+  content: string,
+  filePath: string,
+  options: BarrelLoaderOptions
+): string | null {
+  if (!nativeAddon?.process_with_dependencies_napi) return null;
+  const result = nativeAddon.process_with_dependencies_napi(
+    content,
+    filePath,
+    nativeOptions(options),
+    createNativeFileSystem(context.fs ?? fs)
+  );
+  reportDependencies(context, result);
+  return result.code;
+}
+
+/**
+ * Resolve, deduplicate, sort and reconstruct the barrel in TypeScript, reading
+ * through a filesystem that records dependencies for watch mode
+ */
+function processInTypeScript(
+  context: LoaderContext<BarrelLoaderOptions>,
+  filePath: string,
+  options: BarrelLoaderOptions,
+  verbose: boolean
+): string {
+  // Read through webpack's inputFileSystem so virtual modules and memfs resolve,
+  // recording what was read and probed so watch mode sees leaf changes
+  const tracked = trackDependencies(context.fs ?? fs);
+  const exports = resolveBarrelExportsRecursive(filePath, tracked.fileSystem, options);
+  reportDependencies(context, tracked.dependencies);
+  logVerbose(verbose, 'Resolved exports', {
+    total: exports.length,
+    typeExports: exports.filter((exp) => exp.is_type_export).length,
+    namespaceExports: exports.filter((exp) => exp.export_type === 'namespace').length,
+  });
+
+  const dedupedExports = getDedupedExports(exports);
+  logVerbose(verbose, 'Removed duplicates', { total: dedupedExports.length });
+
+  const sortedExports = sortExports(dedupedExports);
+  logVerbose(verbose, 'Sorted exports', { total: sortedExports.length });
+
+  const result = reconstructSource(sortedExports);
+  logVerbose(verbose, 'Reconstructed source', {});
+  return result;
+}
+
 /**
  * Main loader function for webpack/rspack
  * Compatible with both @rspack/core and webpack loader context
  */
 function barrelLoaderRust(
   this: LoaderContext<BarrelLoaderOptions>,
-  _content: string
+  content: string
 ): string | undefined {
   const filePath = this.resourcePath || this.resource;
 
@@ -43,21 +111,9 @@ function barrelLoaderRust(
 
   logVerbose(verbose, 'Start', { filePath });
 
-  const exports = resolveBarrelExportsRecursive(filePath, fs, options);
-  logVerbose(verbose, 'Resolved exports', {
-    total: exports.length,
-    typeExports: exports.filter((exp) => exp.is_type_export).length,
-    namespaceExports: exports.filter((exp) => exp.export_type === 'namespace').length,
-  });
-
-  const dedupedExports = getDedupedExports(exports);
-  logVerbose(verbose, 'Removed duplicates', { total: dedupedExports.length });
-
-  const sortedExports = sortExports(dedupedExports);
-  logVerbose(verbose, 'Sorted exports', { total: sortedExports.length });
-
-  const result = reconstructSource(sortedExports);
-  logVerbose(verbose, 'Reconstructed source', {});
+  const native = processNatively(this, content, filePath, options);
+  if (native !== null) logVerbose(verbose, 'Processed natively', {});
+  const result = native ?? processInTypeScript(this, filePath, options, verbose);
 
   if (this.sourceMap && this.callback) {
     this.callback(null, result, null);
@@ -9,7 +9,129 @@ interface ExportInfo {
   is_type_export: boolean;
 }
 
+interface ExportConflict {
+  name: string;
+  origins: ExportInfo[];
+}
+
+/**
+ * One re-export statement on a cycle: `file` re-exports `source` at `line`
+ */
+interface CycleStep {
+  file: string;
+  line: number;
+  source: string;
+}
+
+/**
+ * Re-export chain leading back to its first file; the last step's `source`
+ * resolves to the first step's `file`
+ */
+interface ExportCycle {
+  steps: CycleStep[];
+}
+
+/**
+ * Counters of a module cache directory since the process started, and what it
+ * currently stores
+ */
+interface CacheStats {
+  hits: number;
+  misses: number;
+  writes: number;
+  evictions: number;
+  entries: number;
+  bytes: number;
+}
+
+/**
+ * Transformed barrel and the paths it was built from, for webpack's watch mode.
+ * `missingDependencies` lists paths that were probed and not found.
+ */
+interface ProcessResult {
+  code: string;
+  fileDependencies: string[];
+  contextDependencies: string[];
+  missingDependencies: string[];
+  /** JSON `ExportManifest`, when the `manifest` option is enabled */
+  manifest?: string;
+}
+
+/**
+ * Origin of every name a barrel exports, described by `docs/manifest.schema.json`.
+ * `via` lists the barrels a name was re-exported through, outermost first.
+ */
+interface ExportManifest {
+  version: 1;
+  barrel: string;
+  exports: Record<
+    string,
+    { file: string | null; local: string; kind: 'value' | 'type'; via: string[] }
+  >;
+  stars: { source: string; file: string | null }[];
+}
+
+/**
+ * A barrel whose flattened exports changed; `*` stands for star exports kept as is
+ */
+interface AffectedBarrel {
+  barrel: string;
+  names: string[];
+}
+
+/**
+ * Whether importing a module runs code. `reason` is `package.json` when the
+ * package's `sideEffects` field decided, `analysis` when the module's own
+ * top-level statements did, or `unreadable`.
+ */
+interface ModuleSideEffects {
+  file: string;
+  pure: boolean;
+  reason: 'package.json' | 'analysis' | 'unreadable';
+  lines: number[];
+}
+
+/**
+ * Reverse dependency graph of barrels kept alive between rebuilds. `update`
+ * re-resolves only barrels whose re-export structure changed.
+ */
+interface NativeExportGraph {
+  addBarrel: (barrel: string, fileSystem?: NativeFileSystem) => void;
+  removeBarrel: (barrel: string) => boolean;
+  barrels: () => string[];
+  dependents: (file: string) => string[];
+  update: (changed: string[], fileSystem?: NativeFileSystem) => AffectedBarrel[];
+}
+
+type ConflictPolicy = 'ignore' | 'error' | 'warn' | 'first-wins' | 'last-wins';
+
+type SortStrategy =
+  | 'alphabetical'
+  | 'natural'
+  | 'case-insensitive'
+  | 'preserve'
+  | 'externals-first'
+  | 'kind';
+
+type ModuleResolution = 'node10' | 'node16' | 'nodenext' | 'bundler';
+
+type SourceStyle =
+  | 'relative'
+  | 'extensionless'
+  | 'extension'
+  | 'alias'
+  | 'fully-specified'
+  | 'absolute';
+
 interface BarrelLoaderOptions {
+  /**
+   * Preset that turns on the full native pipeline: resolution, namespace
+   * expansion, conflict handling (`warn`), dedup and sorting.
+   * Options set explicitly override the preset.
+   * @default false
+   */
+  optimize?: boolean;
+
   /**
    * Enable/disable recursive barrel file resolution
    * @default true
@@ -17,28 +139,173 @@ interface BarrelLoaderOptions {
   resolveBarrelFiles?: boolean;
 
   /**
-   * Remove duplicate exports
+   * Follow re-export chains to leaf modules natively in Rust (`processBarrelFile`).
+   * The loader defaults it to `resolveBarrelFiles`.
+   * @default false
+   */
+  resolveBarrelExports?: boolean;
+
+  /**
+   * tsconfig used to resolve `paths`/`baseUrl` aliases in re-export sources.
+   * The nearest `tsconfig.json` above the barrel is used when unset.
+   */
+  tsconfig?: string;
+
+  /**
+   * Follow bare specifiers into `node_modules` packages using `exports`
+   * (with conditions), `module`, `main` and `types`. Useful for workspace barrels.
+   * @default false
+   */
+  resolvePackages?: boolean;
+
+  /**
+   * Import map files (`imports` and `scopes`) applied to re-export sources before
+   * file probing. URL specifiers (`https:`, `npm:`, `jsr:`) are left untouched.
+   */
+  importMaps?: string[];
+
+  /**
+   * Extensions tried for extensionless sources, in order (webpack `resolve.extensions`)
+   * @default ['.ts', '.tsx', '.js', '.jsx', '.mjs', '.mts', '.cjs', '.cts']
+   */
+  extensions?: string[];
+
+  /**
+   * File names tried inside directories (webpack `resolve.mainFiles`)
+   * @default ['index']
+   */
+  mainFiles?: string[];
+
+  /**
+   * Conditions matched in package `exports`/`imports`, besides `default`
+   * (webpack `resolve.conditionNames`)
+   * @default ['import', 'module']
+   */
+  conditionNames?: string[];
+
+  /**
+   * `package.json` fields used when a package has no `exports` (webpack `resolve.mainFields`)
+   * @default ['module', 'main', 'types']
+   */
+  mainFields?: string[];
+
+  /**
+   * TypeScript module resolution mode. `node16`, `nodenext` and `bundler` map `.js`
+   * specifiers to their `.ts` sources. Defaults to the tsconfig's setting.
+   */
+  moduleResolution?: ModuleResolution;
+
+  /**
+   * Output the real path of symlinked modules, as with pnpm's `node_modules` links
+   * (webpack `resolve.symlinks`). Modules are deduplicated by real path either way.
+   * @default true
+   */
+  symlinks?: boolean;
+
+  /**
+   * How flattened sources are written, relative to the barrel being processed:
+   * `relative` (`./ui/button`, `./ui` for an index file), `extensionless`
+   * (`./ui/index`), `extension` (`./ui/button.tsx`), `alias` (tsconfig `paths`),
+   * `fully-specified` (`./ui/button.js`, `./ui/index.js`, for native ESM) or
+   * `absolute`. Shortened forms are only used when they resolve back.
+   * @default 'relative'
+   */
+  sourceStyle?: SourceStyle;
+
+  /**
+   * Remove duplicate exports. With `resolveBarrelExports` sources are compared by
+   * the file they resolve to, so `./Button` and `./Button/index.ts` are merged
+   * under the shorter specifier; otherwise only `.`/`..` segments are normalized.
    * @default true
    */
   removeDuplicates?: boolean;
 
   /**
-   * Sort exports alphabetically
+   * Sort exports alphabetically. The loader sorts unless this is `false`.
    * @default false
    */
   sort?: boolean;
 
+  /**
+   * Order of export statements; setting it enables sorting
+   * @default 'alphabetical'
+   */
+  sortStrategy?: SortStrategy;
+
+  /**
+   * Order of specifiers inside one statement, independent of statement order
+   * @default 'alphabetical'
+   */
+  specifierSortStrategy?: Extract<
+    SortStrategy,
+    'alphabetical' | 'natural' | 'case-insensitive' | 'preserve'
+  >;
+
   /**
    * Convert namespace exports to named exports
    * @default false
    */
   convertNamespaceToNamed?: boolean;
 
+  /**
+   * Names consumers import from the barrel, e.g. from webpack's `usedExports`.
+   * Other exports are dropped unless their module has side effects; star
+   * exports are kept.
+   */
+  usedExports?: string[];
+
+  /**
+   * Keep type exports when pruning to `usedExports`
+   * @default true
+   */
+  keepTypeExports?: boolean;
+
+  /**
+   * Return a versioned JSON manifest tracing every export to the module
+   * declaring it, next to the code from `process_with_dependencies_napi`
+   * @default false
+   */
+  manifest?: boolean;
+
+  /**
+   * How to handle the same export name coming from different sources
+   * - `error`: fail the build and report every origin
+   * - `warn`: warn and drop the name (ECMAScript `export *` behavior)
+   * - `first-wins` / `last-wins`: keep the first or last source
+   * - `ignore`: keep every export and only log conflicts in verbose mode
+   * Names two `export *` statements provide from different modules are
+   * conflicts as well
+   * @default 'ignore' ('warn' with `optimize`)
+   */
+  conflictPolicy?: ConflictPolicy;
+
   /**
    * Enable verbose logging
    * @default false
    */
   verbose?: boolean;
+
+  /**
+   * Threads the native resolver uses to read, parse and resolve the barrel graph
+   * ahead of resolution. Output order does not depend on it; `1` reads sequentially. Reads
+   * through webpack's `inputFileSystem` always stay on the main thread.
+   * @default the number of available cores
+   */
+  threads?: number;
+
+  /**
+   * Directory storing parsed modules by content hash and loader version, so warm
+   * builds skip parsing unchanged files. Safe to share between parallel builds.
+   * @default undefined (no cache)
+   */
+  cacheDirectory?: string;
+
+  /**
+   * Size limit of `cacheDirectory` in megabytes; least recently used entries are
+   * removed beyond it
+   * @default 256
+   */
+  cacheMaxSize?: number;
 }
 
 interface LoaderContext<T = unknown> {
@@ -48,18 +315,114 @@ interface LoaderContext<T = unknown> {
   sourceMap?: boolean;
   callback?: (err: Error | null, content?: string, sourceMap?: unknown) => void;
   getOptions?: () => T;
+  addDependency?: (file: string) => void;
+  addContextDependency?: (context: string) => void;
+  addMissingDependency?: (file: string) => void;
+}
+
+/**
+ * Filesystem adapter passed to the native addon; every method returns `null`
+ * for a missing path instead of throwing
+ */
+interface NativeFileSystem {
+  readFile: (filePath: string) => string | null;
+  stat: (
+    filePath: string
+  ) => { isFile: boolean; isDirectory: boolean; mtimeMs?: number } | null;
+  realpath: (filePath: string) => string | null;
+  readDir: (filePath: string) => string[] | null;
 }
 
 interface NativeAddon {
   process_barrel_file?: (
     content: string,
     filePath: string,
-    options?: BarrelLoaderOptions
+    options?: BarrelLoaderOptions,
+    fileSystem?: NativeFileSystem
   ) => string;
+  process_with_dependencies_napi?: (
+    content: string,
+    filePath: string,
+    options?: BarrelLoaderOptions,
+    fileSystem?: NativeFileSystem
+  ) => ProcessResult;
   parse_exports_napi?: (content: string) => ExportInfo[];
   remove_duplicates?: (exports: ExportInfo[]) => ExportInfo[];
   sort_exports_napi?: (exports: ExportInfo[]) => ExportInfo[];
   reconstruct_source_napi?: (source: string, exports: ExportInfo[]) => string;
+  resolve_barrel_napi?: (
+    filePath: string,
+    options?: BarrelLoaderOptions,
+    fileSystem?: NativeFileSystem
+  ) => ExportInfo[];
+  resolve_source_napi?: (
+    importer: string,
+    specifier: string,
+    options?: BarrelLoaderOptions,
+    fileSystem?: NativeFileSystem
+  ) => string | null;
+  source_specifier_napi?: (
+    importer: string,
+    file: string,
+    options?: BarrelLoaderOptions,
+    fileSystem?: NativeFileSystem
+  ) => string;
+  resolve_sources_napi?: (
+    importer: string,
+    specifiers: string[],
+    options?: BarrelLoaderOptions,
+    fileSystem?: NativeFileSystem
+  ) => (string | null)[];
+  rebase_sources_napi?: (
+    from: string,
+    importer: string,
+    sources: string[],
+    options?: BarrelLoaderOptions,
+    fileSystem?: NativeFileSystem
+  ) => string[];
+  detect_conflicts_napi?: (exports: ExportInfo[]) => ExportConflict[];
+  detect_cycles_napi?: (
+    filePath: string,
+    options?: BarrelLoaderOptions,
+    fileSystem?: NativeFileSystem
+  ) => ExportCycle[];
+  analyze_side_effects_napi?: (
+    filePath: string,
+    options?: BarrelLoaderOptions,
+    fileSystem?: NativeFileSystem
+  ) => ModuleSideEffects[];
+  sort_exports_by_strategy_napi?: (
+    exports: ExportInfo[],
+    strategy?: SortStrategy,
+    specifierStrategy?: SortStrategy
+  ) => ExportInfo[];
+  rewrite_imports_napi?: (
+    content: string,
+    filePath: string,
+    options?: BarrelLoaderOptions,
+    fileSystem?: NativeFileSystem
+  ) => string;
+  module_cache_stats_napi?: (cacheDirectory: string) => CacheStats;
+  ExportGraph?: new (options?: BarrelLoaderOptions) => NativeExportGraph;
 }
 
-export type { ExportInfo, BarrelLoaderOptions, LoaderContext, NativeAddon };
+export type {
+  ExportInfo,
+  ExportConflict,
+  CycleStep,
+  ExportCycle,
+  CacheStats,
+  ProcessResult,
+  ExportManifest,
+  AffectedBarrel,
+  ModuleSideEffects,
+  NativeExportGraph,
+  ConflictPolicy,
+  SortStrategy,
+  ModuleResolution,
+  SourceStyle,
+  BarrelLoaderOptions,
+  LoaderContext,
+  NativeAddon,
+  NativeFileSystem,
+};
@@ -2,14 +2,31 @@ mod rs_utils;
 mod types;
 
 // Re-export public API
-pub use rs_utils::barrel_loader::BarrelLoader;
-pub use types::{BarrelLoaderOptions, ExportInfo};
+pub use rs_utils::barrel_loader::{BarrelLoader, PipelineConfig};
+pub use rs_utils::conflicts::ConflictPolicy;
+pub use rs_utils::export_graph::ExportGraph;
+pub use rs_utils::file_system::{
+    DependencyLog, FileSystem, MemoryFileSystem, Metadata, NapiFileSystem, OsFileSystem,
+    TrackingFileSystem,
+};
+pub use rs_utils::import_map::{ImportMap, MappedSpecifier};
+pub use rs_utils::manifest::{ExportManifest, ManifestEntry, ManifestStar, MANIFEST_VERSION};
+pub use rs_utils::module_cache::ModuleCache;
+pub use rs_utils::resolver::{
+    describe_cycle, ModuleResolution, ResolveOptions, ResolvedExport, Resolver, SourceStyle,
+};
+pub use rs_utils::sorting::SortStrategy;
+pub use rs_utils::tsconfig::TsConfig;
+pub use types::{
+    AffectedBarrel, BarrelLoaderOptions, CacheStats, CycleStep, ExportConflict, ExportCycle,
+    ExportInfo, FileStats, ModuleSideEffects, ProcessResult,
+};
 
 // Re-export NAPI bindings
 pub use rs_utils::napi_bindings::{
-    parse_exports_napi,
-    process_barrel_file,
-    reconstruct_source_napi,
-    remove_duplicates,
-    sort_exports_napi,
+    analyze_side_effects_napi, detect_conflicts_napi, detect_cycles_napi, module_cache_stats_napi,
+    parse_exports_napi, process_barrel_file, process_with_dependencies_napi, rebase_sources_napi,
+    reconstruct_source_napi, remove_duplicates, resolve_barrel_napi, resolve_source_napi,
+    resolve_sources_napi, rewrite_imports_napi, sort_exports_by_strategy_napi, sort_exports_napi,
+    source_specifier_napi, ExportGraphNapi,
 };
@@ -0,0 +1,125 @@
+use super::super::conflicts::ConflictPolicy;
+use super::super::module_cache::DEFAULT_MAX_SIZE;
+use super::super::resolver::{ModuleResolution, ResolveOptions, SourceStyle};
+use super::super::sorting::SortStrategy;
+use crate::types::BarrelLoaderOptions;
+use std::collections::HashSet;
+use std::path::PathBuf;
+
+/// Pipeline settings after applying the `optimize` preset
+/// `optimize` turns every stage on; any option set explicitly overrides the preset
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[allow(clippy::struct_excessive_bools)]
+pub struct PipelineConfig {
+    pub resolve_barrel_exports: bool,
+    pub tsconfig: Option<PathBuf>,
+    pub resolve_packages: bool,
+    pub import_maps: Vec<PathBuf>,
+    pub resolve_options: ResolveOptions,
+    /// Explicit `moduleResolution`; the tsconfig's setting applies when unset
+    pub module_resolution: Option<ModuleResolution>,
+    pub symlinks: bool,
+    /// How sources are written once exports are traced to their leaf modules
+    pub source_style: SourceStyle,
+    pub convert_namespace_to_named: bool,
+    /// Names to keep when pruning unused exports; pruning is off when unset
+    pub used_exports: Option<HashSet<String>>,
+    pub keep_type_exports: bool,
+    pub manifest: bool,
+    pub remove_duplicates: bool,
+    pub conflict_policy: ConflictPolicy,
+    pub sort: bool,
+    pub sort_strategy: SortStrategy,
+    pub specifier_sort_strategy: SortStrategy,
+    pub verbose: bool,
+    /// Threads reading and parsing modules ahead of resolution; 1 disables prefetching
+    pub threads: usize,
+    pub cache_directory: Option<PathBuf>,
+    /// Cache directory size limit in bytes
+    pub cache_max_size: u64,
+}
+
+impl PipelineConfig {
+    /// Resolve loader options into concrete pipeline settings
+    pub fn from_options(options: &BarrelLoaderOptions) -> Result<Self, String> {
+        let optimize = options.optimize.unwrap_or(false);
+        let has_strategy =
+            options.sort_strategy.is_some() || options.specifier_sort_strategy.is_some();
+
+        Ok(Self {
+            resolve_barrel_exports: options.resolve_barrel_exports.unwrap_or(optimize),
+            tsconfig: options.tsconfig.as_ref().map(PathBuf::from),
+            resolve_packages: options.resolve_packages.unwrap_or(false),
+            import_maps: options
+                .import_maps
+                .iter()
+                .flatten()
+                .map(PathBuf::from)
+                .collect(),
+            resolve_options: resolve_options(options),
+            module_resolution: options
+                .module_resolution
+                .as_deref()
+                .map(ModuleResolution::parse)
+                .transpose()?,
+            symlinks: options.symlinks.unwrap_or(true),
+            source_style: options
+                .source_style
+                .as_deref()
+                .map(SourceStyle::parse)
+                .transpose()?
+                .unwrap_or_default(),
+            convert_namespace_to_named: options.convert_namespace_to_named.unwrap_or(optimize),
+            used_exports: options.used_exports.clone().map(HashSet::from_iter),
+            keep_type_exports: options.keep_type_exports.unwrap_or(true),
+            manifest: options.manifest.unwrap_or(false),
+            remove_duplicates: options.remove_duplicates.unwrap_or(true),
+            conflict_policy: conflict_policy(options, optimize)?,
+            sort: options.sort.unwrap_or(optimize || has_strategy),
+            sort_strategy: SortStrategy::from_option(options.sort_strategy.as_deref())?,
+            specifier_sort_strategy: SortStrategy::from_option(
+                options.specifier_sort_strategy.as_deref(),
+            )?,
+            verbose: options.verbose.unwrap_or(false),
+            cache_directory: options.cache_directory.as_ref().map(PathBuf::from),
+            cache_max_size: options
+                .cache_max_size
+                .map_or(DEFAULT_MAX_SIZE, |megabytes| u64::from(megabytes) * 1024 * 1024),
+            threads: options
+                .threads
+                .map_or_else(default_threads, |threads| threads as usize),
+        })
+    }
+}
+
+/// The configured conflict policy; `optimize` drops ambiguous names with a warning
+fn conflict_policy(
+    options: &BarrelLoaderOptions,
+    optimize: bool,
+) -> Result<ConflictPolicy, String> {
+    match options.conflict_policy.as_deref() {
+        Some(policy) => ConflictPolicy::parse(policy),
+        None if optimize => Ok(ConflictPolicy::Warn),
+        None => Ok(ConflictPolicy::Ignore),
+    }
+}
+
+/// Apply the webpack-style resolution options over the defaults
+fn resolve_options(options: &BarrelLoaderOptions) -> ResolveOptions {
+    let defaults = ResolveOptions::default();
+    ResolveOptions {
+        extensions: options.extensions.clone().unwrap_or(defaults.extensions),
+        main_files: options.main_files.clone().unwrap_or(defaults.main_files),
+        condition_names: options
+            .condition_names
+            .clone()
+            .unwrap_or(defaults.condition_names),
+        main_fields: options.main_fields.clone().unwrap_or(defaults.main_fields),
+        extension_alias: defaults.extension_alias,
+    }
+}
+
+/// One thread per available core
+fn default_threads() -> usize {
+    std::thread::available_parallelism().map_or(1, std::num::NonZeroUsize::get)
+}
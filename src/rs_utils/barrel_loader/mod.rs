@@ -1,19 +1,34 @@
+mod config;
 mod file_check;
 mod processor;
 
-use crate::types::BarrelLoaderOptions;
+use super::file_system::{FileSystem, OsFileSystem};
+use super::import_rewrite::rewrite_imports;
+use super::resolver::Resolver;
+use crate::types::{BarrelLoaderOptions, ExportCycle, ModuleSideEffects, ProcessResult};
+pub use config::PipelineConfig;
 pub use file_check::is_barrel_file;
-use processor::process_file;
+use processor::{create_resolver, process_file};
+use std::path::{Path, PathBuf};
 
 /// Main barrel loader
-pub struct BarrelLoader {
+pub struct BarrelLoader<F = OsFileSystem> {
     options: BarrelLoaderOptions,
+    fs: F,
 }
 
 impl BarrelLoader {
     #[must_use]
     pub const fn new(options: BarrelLoaderOptions) -> Self {
-        Self { options }
+        Self { options, fs: OsFileSystem }
+    }
+}
+
+impl<F: FileSystem> BarrelLoader<F> {
+    /// Read modules through another filesystem, such as webpack's `inputFileSystem`
+    #[must_use]
+    pub fn with_file_system<G: FileSystem>(self, fs: G) -> BarrelLoader<G> {
+        BarrelLoader { options: self.options, fs }
     }
 
     /// Check if a file is a barrel file
@@ -24,10 +39,133 @@ impl BarrelLoader {
 
     /// Process a barrel file
     pub fn process(&self, source: &str, file_path: &str) -> Result<String, String> {
+        self.process_with_dependencies(source, file_path)
+            .map(|result| result.code)
+    }
+
+    /// Process a barrel file and report the files, directories and missing paths
+    /// its output depends on, for `addDependency` in watch mode
+    pub fn process_with_dependencies(
+        &self,
+        source: &str,
+        file_path: &str,
+    ) -> Result<ProcessResult, String> {
         if !self.is_barrel_file(file_path) {
-            return Ok(source.to_string());
+            return Ok(ProcessResult { code: source.to_string(), ..ProcessResult::default() });
         }
 
-        process_file(source, file_path, &self.options)
+        let config: PipelineConfig = PipelineConfig::from_options(&self.options)?;
+        process_file(&self.fs, source, file_path, &config)
+    }
+
+    /// Resolve a specifier from `importer` with the same settings the pipeline uses
+    /// Returns `None` when the specifier does not resolve to a file
+    pub fn resolve_source(
+        &self,
+        importer: &str,
+        specifier: &str,
+    ) -> Result<Option<PathBuf>, String> {
+        let config: PipelineConfig = PipelineConfig::from_options(&self.options)?;
+        let resolver = create_resolver(&self.fs, importer, &config)?;
+        Ok(resolver.resolve_source(Path::new(importer), specifier))
+    }
+
+    /// Resolve every source of a barrel with one resolver, so tsconfig and
+    /// import maps are read once; unresolvable sources give `None`
+    pub fn resolve_sources(
+        &self,
+        importer: &str,
+        specifiers: &[String],
+    ) -> Result<Vec<Option<PathBuf>>, String> {
+        let config: PipelineConfig = PipelineConfig::from_options(&self.options)?;
+        let resolver = create_resolver(&self.fs, importer, &config)?;
+        Ok(specifiers
+            .iter()
+            .map(|specifier| resolver.resolve_source(Path::new(importer), specifier))
+            .collect())
+    }
+
+    /// Rewrite sources written in `from` so they point at the same modules from
+    /// `importer`, in the configured `sourceStyle`; unresolvable sources are kept
+    pub fn rebase_sources(
+        &self,
+        from: &str,
+        importer: &str,
+        sources: &[String],
+    ) -> Result<Vec<String>, String> {
+        let config: PipelineConfig = PipelineConfig::from_options(&self.options)?;
+        let resolver = create_resolver(&self.fs, importer, &config)?;
+        Ok(sources
+            .iter()
+            .map(|source| {
+                resolver
+                    .resolve_source(Path::new(from), source)
+                    .map_or_else(
+                        || source.clone(),
+                        |file| {
+                            resolver.source_specifier(
+                                Path::new(importer),
+                                &file,
+                                config.source_style,
+                            )
+                        },
+                    )
+            })
+            .collect())
+    }
+
+    /// Resolver configured like the pipeline, for tools walking the barrel graph
+    pub fn resolver(&self, importer: &str) -> Result<Resolver<&F>, String> {
+        let config: PipelineConfig = PipelineConfig::from_options(&self.options)?;
+        create_resolver(&self.fs, importer, &config)
+    }
+
+    /// Write the source pointing `importer` at `file` in the configured `sourceStyle`
+    pub fn source_specifier(&self, importer: &str, file: &str) -> Result<String, String> {
+        let config: PipelineConfig = PipelineConfig::from_options(&self.options)?;
+        let resolver = create_resolver(&self.fs, importer, &config)?;
+        Ok(resolver.source_specifier(Path::new(importer), Path::new(file), config.source_style))
+    }
+
+    /// Rewrite a consumer module's barrel imports to the modules declaring each name
+    /// Relative imports are written in the configured `sourceStyle`
+    pub fn rewrite_imports(&self, source: &str, file_path: &str) -> Result<String, String> {
+        let config: PipelineConfig = PipelineConfig::from_options(&self.options)?;
+        let resolver = create_resolver(&self.fs, file_path, &config)?;
+        Ok(rewrite_imports(&resolver, source, Path::new(file_path), config.source_style))
+    }
+
+    /// Find the re-export cycles reachable from a barrel
+    /// Both named re-exports and every `export *` target are followed
+    pub fn find_cycles(&self, file_path: &str) -> Result<Vec<ExportCycle>, String> {
+        let config: PipelineConfig = PipelineConfig::from_options(&self.options)?;
+        let resolver = create_resolver(&self.fs, file_path, &config)?;
+        let barrel = Path::new(file_path);
+        let module = resolver.load(barrel)?;
+        let _ = resolver.resolve_exports(barrel, &module.exports);
+        let _ = resolver.expand_namespace_exports(barrel, &module.exports);
+        Ok(resolver.cycles())
+    }
+
+    /// Give every module reachable from a barrel a pure or impure verdict
+    /// Flattening and import rewriting never skip the impure ones
+    pub fn analyze_side_effects(&self, file_path: &str) -> Result<Vec<ModuleSideEffects>, String> {
+        let config: PipelineConfig = PipelineConfig::from_options(&self.options)?;
+        let resolver = create_resolver(&self.fs, file_path, &config)?;
+        let barrel = Path::new(file_path);
+        let module = resolver.load(barrel)?;
+        let _ = resolver.resolve_exports(barrel, &module.exports);
+        let _ = resolver.expand_namespace_exports(barrel, &module.exports);
+
+        let mut modules: Vec<PathBuf> = resolver
+            .modules()
+            .into_iter()
+            .map(|(path, _)| path)
+            .collect();
+        modules.sort();
+        Ok(modules
+            .iter()
+            .map(|path| resolver.side_effects(path))
+            .collect())
     }
 }
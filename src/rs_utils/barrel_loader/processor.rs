@@ -1,55 +1,411 @@
-use super::super::deduplication::remove_duplicates;
-use super::super::parser::parse_exports;
+use super::super::conflicts::{
+    describe_conflict, detect_conflicts, resolve_conflicts, ConflictPolicy,
+};
+use super::super::deduplication::{normalize_source, remove_duplicates, remove_duplicates_by};
+use super::super::file_system::{DependencyLog, FileSystem};
+use super::super::import_map::ImportMap;
+use super::super::manifest::build_manifest;
+use super::super::module_cache::ModuleCache;
+use super::super::parser::{find_reexports, parse_exports};
+use super::super::pruning::prune_exports;
 use super::super::reconstruction::reconstruct_source;
-use super::super::sorting::sort_exports;
-use crate::types::BarrelLoaderOptions;
+use super::super::resolver::{describe_cycle, ModuleResolution, Resolver, SourceStyle};
+use super::super::sorting::sort_exports_by;
+use super::super::specifier::exported_name;
+use super::super::tsconfig::TsConfig;
+use super::config::PipelineConfig;
+use crate::types::{ExportConflict, ExportInfo, ProcessResult};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
 
-/// Process a barrel file with the given options
-pub fn process_file(
+/// Process a barrel file with the given pipeline settings, recording every path
+/// read or probed on the way
+pub fn process_file<F: FileSystem>(
+    fs: &F,
     source: &str,
     file_path: &str,
-    options: &BarrelLoaderOptions,
+    config: &PipelineConfig,
+) -> Result<ProcessResult, String> {
+    let log = DependencyLog::new();
+    let tracked = log.track(fs);
+    let code = transform(&tracked, source, file_path, config)?;
+    let manifest = if config.manifest {
+        Some(manifest_json(&tracked, source, file_path, config)?)
+    } else {
+        None
+    };
+    let strings = |paths: Vec<PathBuf>| -> Vec<String> {
+        paths
+            .into_iter()
+            .map(|path| path.to_string_lossy().into_owned())
+            .collect()
+    };
+    Ok(ProcessResult {
+        code,
+        file_dependencies: strings(log.files()),
+        context_dependencies: strings(log.contexts()),
+        missing_dependencies: strings(log.missing()),
+        manifest,
+    })
+}
+
+/// Trace the barrel's exports to their declarations for the JSON manifest
+fn manifest_json<F: FileSystem>(
+    fs: &F,
+    source: &str,
+    file_path: &str,
+    config: &PipelineConfig,
+) -> Result<String, String> {
+    let resolver = create_resolver(fs, file_path, config)?.with_side_effect_barriers(false);
+    let manifest = build_manifest(&resolver, source, Path::new(file_path))?;
+    serde_json::to_string_pretty(&manifest)
+        .map_err(|e| format!("Failed to write manifest for {file_path}: {e}"))
+}
+
+fn transform<F: FileSystem>(
+    fs: &F,
+    source: &str,
+    file_path: &str,
+    config: &PipelineConfig,
 ) -> Result<String, String> {
-    if options.verbose.unwrap_or(false) {
+    if config.verbose {
         eprintln!("[barrel-loader] Processing barrel file: {file_path}");
     }
 
-    let mut exports: Vec<crate::ExportInfo> = parse_exports(source)?;
+    let mut exports: Vec<ExportInfo> = parse_exports(source)?;
 
     if exports.is_empty() {
-        if options.verbose.unwrap_or(false) {
+        if config.verbose {
             eprintln!("[barrel-loader] No exports found in: {file_path}");
         }
         return Ok(source.to_string());
     }
 
+    let resolver = if config.resolve_barrel_exports
+        || config.convert_namespace_to_named
+        || config.source_style == SourceStyle::FullySpecified
+        || config.used_exports.is_some()
+        || checks_star_conflicts(config)
+    {
+        create_resolver(fs, file_path, config)?
+    } else {
+        Resolver::with_file_system(fs)
+    };
+
+    // Read and parse the barrel graph in parallel before walking it
+    if config.resolve_barrel_exports || config.convert_namespace_to_named {
+        resolver.prefetch(Path::new(file_path), &exports, config.threads);
+    }
+
+    // Apply the conflict policy to names several `export *` provide
+    exports = handle_star_conflicts(&resolver, exports, file_path, config)?;
+
+    // Follow re-export chains down to leaf modules
+    if config.resolve_barrel_exports {
+        exports = resolve_exports(&resolver, &exports, file_path, config);
+    }
+
+    // Expand `export *` into explicit named and type exports
+    if config.convert_namespace_to_named {
+        exports = resolver.expand_namespace_exports(Path::new(file_path), &exports);
+    }
+    log_cycles(&resolver, file_path, config);
+
+    // Node's ESM loader needs the runtime extension on every relative source
+    if config.source_style == SourceStyle::FullySpecified {
+        exports = resolver.specify_sources(Path::new(file_path), exports);
+    }
+
     // Remove duplicates if requested
-    if options.remove_duplicates.unwrap_or(true) {
-        let before: usize = exports.len();
-        exports = remove_duplicates(exports);
-        if options.verbose.unwrap_or(false) && exports.len() < before {
-            eprintln!(
-                "[barrel-loader] Removed {} duplicate exports from: {}",
-                before - exports.len(),
-                file_path
-            );
-        }
+    if config.remove_duplicates {
+        exports = dedupe_exports(&resolver, exports, file_path, config);
     }
 
+    let conflicts = detect_conflicts(&exports);
+    exports = handle_conflicts(exports, &conflicts, file_path, config)?;
+
     // Sort exports if requested
-    if options.sort.unwrap_or(false) {
-        exports = sort_exports(exports);
-        if options.verbose.unwrap_or(false) {
-            eprintln!("[barrel-loader] Sorted exports in: {file_path}");
-        }
+    if config.sort {
+        exports = sort_exports(exports, file_path, config);
     }
 
-    // Reconstruct source
-    let transformed: String = reconstruct_source(source, exports);
+    // Keep only the exports consumers import
+    if let Some(used) = &config.used_exports {
+        exports = prune_unused(&resolver, exports, used, file_path, config);
+    }
+
+    Ok(reconstruct(source, exports, file_path, config))
+}
+
+/// Regenerate the barrel's source from its final exports
+/// A barrel whose exports were all pruned or dropped as conflicts keeps its other
+/// statements and stays a module
+fn reconstruct(
+    source: &str,
+    exports: Vec<ExportInfo>,
+    file_path: &str,
+    config: &PipelineConfig,
+) -> String {
+    let transformed: String = if exports.is_empty() {
+        without_exports(source)
+    } else {
+        reconstruct_source(source, exports)
+    };
 
-    if options.verbose.unwrap_or(false) && transformed != source {
+    if config.verbose && transformed != source {
         eprintln!("[barrel-loader] Transformed barrel file: {file_path}");
     }
 
-    Ok(transformed)
+    transformed
+}
+
+/// Create a resolver that understands the project's aliases and import maps
+/// An explicit tsconfig must load; a discovered one is skipped when broken
+pub fn create_resolver<'fs, F: FileSystem>(
+    fs: &'fs F,
+    file_path: &str,
+    config: &PipelineConfig,
+) -> Result<Resolver<&'fs F>, String> {
+    let import_maps = config
+        .import_maps
+        .iter()
+        .map(|path| ImportMap::load(fs, path))
+        .collect::<Result<Vec<_>, _>>()?;
+    let tsconfig = load_tsconfig(fs, file_path, config)?;
+
+    let module_resolution = match config.module_resolution {
+        Some(mode) => Some(mode),
+        None => tsconfig
+            .as_ref()
+            .and_then(|tsconfig| tsconfig.module_resolution.as_deref())
+            .map(ModuleResolution::parse)
+            .transpose()?,
+    };
+    let options = module_resolution.map_or_else(
+        || config.resolve_options.clone(),
+        |mode| config.resolve_options.clone().with_module_resolution(mode),
+    );
+
+    let mut resolver = Resolver::with_file_system(fs)
+        .with_options(options)
+        .with_packages(config.resolve_packages)
+        .with_symlinks(config.symlinks)
+        .with_import_maps(import_maps);
+    if let Some(dir) = &config.cache_directory {
+        resolver = resolver
+            .with_cache(ModuleCache::new(dir.as_path()).with_max_size(config.cache_max_size));
+    }
+    Ok(match tsconfig {
+        Some(tsconfig) => resolver.with_tsconfig(tsconfig),
+        None => resolver,
+    })
+}
+
+/// Load the configured tsconfig, or the nearest one to the barrel
+fn load_tsconfig<F: FileSystem>(
+    fs: &F,
+    file_path: &str,
+    config: &PipelineConfig,
+) -> Result<Option<TsConfig>, String> {
+    if let Some(tsconfig) = &config.tsconfig {
+        return TsConfig::load(fs, tsconfig).map(Some);
+    }
+
+    let dir = Path::new(file_path)
+        .parent()
+        .unwrap_or_else(|| Path::new(""));
+    let Some(found) = TsConfig::find(fs, dir) else {
+        return Ok(None);
+    };
+    match TsConfig::load(fs, &found) {
+        Ok(tsconfig) => Ok(Some(tsconfig)),
+        Err(e) => {
+            if config.verbose {
+                eprintln!("[barrel-loader] Ignoring tsconfig: {e}");
+            }
+            Ok(None)
+        }
+    }
+}
+
+/// Replace each re-export with the leaf modules it points at
+fn resolve_exports<F: FileSystem>(
+    resolver: &Resolver<F>,
+    exports: &[ExportInfo],
+    file_path: &str,
+    config: &PipelineConfig,
+) -> Vec<ExportInfo> {
+    let barrel = Path::new(file_path);
+    let mut entries = resolver.resolve_exports(barrel, exports);
+    if config.remove_duplicates {
+        entries = resolver.dedupe_by_identity(entries);
+    }
+    entries = resolver.rewrite_sources(barrel, entries, config.source_style);
+    let flattened: Vec<ExportInfo> = entries
+        .into_iter()
+        .map(|resolved| resolved.export)
+        .collect();
+    if config.verbose {
+        eprintln!("[barrel-loader] Resolved {} exports from: {file_path}", flattened.len());
+    }
+    flattened
+}
+
+/// Drop repeated exports, comparing sources by the module they resolve to when
+/// resolution is on, and by their normalized spelling otherwise
+fn dedupe_exports<F: FileSystem>(
+    resolver: &Resolver<F>,
+    exports: Vec<ExportInfo>,
+    file_path: &str,
+    config: &PipelineConfig,
+) -> Vec<ExportInfo> {
+    let before: usize = exports.len();
+    let deduped = if config.resolve_barrel_exports {
+        remove_duplicates_by(exports, |source| {
+            resolver
+                .resolve_source(Path::new(file_path), source)
+                .map_or_else(
+                    || normalize_source(source),
+                    |path| path.to_string_lossy().into_owned(),
+                )
+        })
+    } else {
+        remove_duplicates(exports)
+    };
+    if config.verbose && deduped.len() < before {
+        eprintln!(
+            "[barrel-loader] Removed {} duplicate exports from: {}",
+            before - deduped.len(),
+            file_path
+        );
+    }
+    deduped
+}
+
+/// Order statements and their specifiers by the configured strategies
+fn sort_exports(
+    exports: Vec<ExportInfo>,
+    file_path: &str,
+    config: &PipelineConfig,
+) -> Vec<ExportInfo> {
+    let sorted = sort_exports_by(exports, config.sort_strategy, config.specifier_sort_strategy);
+    if config.verbose {
+        eprintln!("[barrel-loader] Sorted exports in: {file_path}");
+    }
+    sorted
+}
+
+/// Drop exports no consumer imports, keeping those whose module has side effects
+/// Sources that do not resolve are assumed to have side effects
+fn prune_unused<F: FileSystem>(
+    resolver: &Resolver<F>,
+    exports: Vec<ExportInfo>,
+    used: &HashSet<String>,
+    file_path: &str,
+    config: &PipelineConfig,
+) -> Vec<ExportInfo> {
+    let before: usize = exports.len();
+    let pruned = prune_exports(exports, used, config.keep_type_exports, |source| {
+        resolver
+            .resolve_source(Path::new(file_path), source)
+            .is_none_or(|path| resolver.has_side_effects(&path))
+    });
+    if config.verbose && pruned.len() < before {
+        eprintln!(
+            "[barrel-loader] Pruned {} unused exports from: {}",
+            before - pruned.len(),
+            file_path
+        );
+    }
+    pruned
+}
+
+/// The barrel without its re-exports once every export was dropped, still a module
+fn without_exports(source: &str) -> String {
+    let mut kept = String::with_capacity(source.len());
+    let mut last = 0;
+    for range in find_reexports(source) {
+        kept.push_str(&source[last..range.start]);
+        let rest = &source[range.end..];
+        last = source.len() - rest.trim_start_matches([' ', '\t']).len();
+    }
+    kept.push_str(&source[last..]);
+    let mut lines: Vec<&str> = kept
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .collect();
+    lines.push("export {};");
+    lines.join("\n") + "\n"
+}
+
+/// Report re-export cycles met while resolving; they are cut where they close
+fn log_cycles<F: FileSystem>(resolver: &Resolver<F>, file_path: &str, config: &PipelineConfig) {
+    if !config.verbose {
+        return;
+    }
+    for cycle in resolver.cycles() {
+        eprintln!(
+            "[barrel-loader] Re-export cycle {} while processing: {file_path}",
+            describe_cycle(&cycle)
+        );
+    }
+}
+
+/// Star exports are only compared when a conflict could be reported or acted on
+fn checks_star_conflicts(config: &PipelineConfig) -> bool {
+    config.conflict_policy != ConflictPolicy::Ignore || config.verbose
+}
+
+/// Apply the conflict policy to names two of the barrel's `export *` provide
+/// from different modules, before stars are flattened or expanded
+fn handle_star_conflicts<F: FileSystem>(
+    resolver: &Resolver<F>,
+    exports: Vec<ExportInfo>,
+    file_path: &str,
+    config: &PipelineConfig,
+) -> Result<Vec<ExportInfo>, String> {
+    let stars = exports
+        .iter()
+        .filter(|exp| exported_name(exp).is_none())
+        .count();
+    if stars < 2 || !checks_star_conflicts(config) {
+        return Ok(exports);
+    }
+    let conflicts = resolver.star_conflicts(Path::new(file_path), &exports);
+    handle_conflicts(exports, &conflicts, file_path, config)
+}
+
+/// Report conflicting exports and apply the conflict policy to them
+fn handle_conflicts(
+    exports: Vec<ExportInfo>,
+    conflicts: &[ExportConflict],
+    file_path: &str,
+    config: &PipelineConfig,
+) -> Result<Vec<ExportInfo>, String> {
+    if conflicts.is_empty() {
+        return Ok(exports);
+    }
+
+    match config.conflict_policy {
+        ConflictPolicy::Ignore if config.verbose => {
+            for conflict in conflicts {
+                eprintln!(
+                    "[barrel-loader] Conflicting {} in: {file_path}",
+                    describe_conflict(conflict)
+                );
+            }
+        }
+        ConflictPolicy::Warn => {
+            for conflict in conflicts {
+                eprintln!(
+                    "[barrel-loader] Warning: dropping ambiguous {} in: {file_path}",
+                    describe_conflict(conflict)
+                );
+            }
+        }
+        _ => {}
+    }
+
+    resolve_conflicts(exports, conflicts, config.conflict_policy)
+        .map_err(|e| format!("{e} in {file_path}"))
 }
@@ -0,0 +1,132 @@
+use super::specifier::exported_name;
+use crate::types::{ExportConflict, ExportInfo};
+
+/// How exports that share a name across different sources are handled
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConflictPolicy {
+    /// Keep every export; conflicts are only reported in verbose mode
+    Ignore,
+    /// Fail processing with a report of every conflict
+    Error,
+    /// Print a warning and drop the ambiguous name, like ECMAScript does for `export *`
+    Warn,
+    /// Keep the name from the source that appears first
+    FirstWins,
+    /// Keep the name from the source that appears last
+    LastWins,
+}
+
+impl ConflictPolicy {
+    /// Parse a policy from its option value
+    pub fn parse(value: &str) -> Result<Self, String> {
+        match value {
+            "ignore" => Ok(Self::Ignore),
+            "error" => Ok(Self::Error),
+            "warn" => Ok(Self::Warn),
+            "first-wins" => Ok(Self::FirstWins),
+            "last-wins" => Ok(Self::LastWins),
+            other => Err(format!(
+                "Unknown conflict policy \"{other}\" (expected ignore, error, warn, first-wins or last-wins)"
+            )),
+        }
+    }
+}
+
+/// Find export names that are provided by more than one source
+/// Origins are listed in the order their sources first appear
+#[must_use]
+pub fn detect_conflicts(exports: &[ExportInfo]) -> Vec<ExportConflict> {
+    let mut conflicts: Vec<ExportConflict> = Vec::new();
+
+    for exp in exports {
+        let Some(name) = exported_name(exp) else {
+            continue;
+        };
+        match conflicts.iter_mut().find(|c| c.name == name) {
+            Some(entry) => {
+                if !entry.origins.iter().any(|o| o.source == exp.source) {
+                    entry.origins.push(exp.clone());
+                }
+            }
+            None => conflicts
+                .push(ExportConflict { name: name.to_string(), origins: vec![exp.clone()] }),
+        }
+    }
+
+    conflicts.retain(|c| c.origins.len() > 1);
+    conflicts
+}
+
+/// Describe a conflict with all of its origins
+#[must_use]
+pub fn describe_conflict(conflict: &ExportConflict) -> String {
+    let origins = conflict
+        .origins
+        .iter()
+        .map(|o| format!("{} (line {})", o.source, o.line))
+        .collect::<Vec<_>>()
+        .join(", ");
+    format!("export \"{}\" is provided by {origins}", conflict.name)
+}
+
+/// Apply a conflict policy to a list of exports
+pub fn resolve_conflicts(
+    exports: Vec<ExportInfo>,
+    conflicts: &[ExportConflict],
+    policy: ConflictPolicy,
+) -> Result<Vec<ExportInfo>, String> {
+    if conflicts.is_empty() || policy == ConflictPolicy::Ignore {
+        return Ok(exports);
+    }
+
+    if policy == ConflictPolicy::Error {
+        let report = conflicts
+            .iter()
+            .map(describe_conflict)
+            .collect::<Vec<_>>()
+            .join("; ");
+        return Err(format!("Conflicting exports: {report}"));
+    }
+
+    let mut kept: Vec<ExportInfo> = exports
+        .into_iter()
+        .filter(|exp| {
+            let Some(name) = exported_name(exp) else {
+                return true;
+            };
+            let Some(conflict) = conflicts.iter().find(|c| c.name == name) else {
+                return true;
+            };
+            winner(conflict, policy).is_some_and(|o| o.source == exp.source)
+        })
+        .collect();
+
+    // Names only `export *` statements provide get the winner as an explicit
+    // export, which shadows the stars, right after the star it comes from
+    for conflict in conflicts {
+        let Some(winner) = winner(conflict, policy) else {
+            continue;
+        };
+        if kept
+            .iter()
+            .any(|exp| exported_name(exp) == Some(conflict.name.as_str()))
+        {
+            continue;
+        }
+        let index = kept
+            .iter()
+            .position(|exp| exp.source == winner.source && exp.line == winner.line)
+            .map_or(kept.len(), |index| index + 1);
+        kept.insert(index, winner.clone());
+    }
+    Ok(kept)
+}
+
+/// The origin a policy keeps, if any
+fn winner(conflict: &ExportConflict, policy: ConflictPolicy) -> Option<&ExportInfo> {
+    match policy {
+        ConflictPolicy::FirstWins => conflict.origins.first(),
+        ConflictPolicy::LastWins => conflict.origins.last(),
+        ConflictPolicy::Warn | ConflictPolicy::Error | ConflictPolicy::Ignore => None,
+    }
+}
@@ -1,19 +1,91 @@
+use super::resolver::{is_relative_specifier, normalize_path};
 use crate::types::ExportInfo;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
 
 /// Remove duplicate exports by creating a unique key for each export
-/// The key is a combination of export type, specifier, source, and type flag
+/// The key is a combination of export type, specifier, normalized source, and type flag
+/// Type-only exports already covered by a value export of the same name are dropped too
 #[must_use]
 pub fn remove_duplicates(exports: Vec<ExportInfo>) -> Vec<ExportInfo> {
-    let mut seen = HashSet::new();
+    remove_duplicates_by(exports, normalize_source)
+}
+
+/// Remove duplicate exports, comparing sources by the module they point at
+/// `identity` maps a source to its module, e.g. the resolved path; the kept
+/// export of a merged group takes the shortest normalized spelling among them
+pub fn remove_duplicates_by(
+    exports: Vec<ExportInfo>,
+    identity: impl Fn(&str) -> String,
+) -> Vec<ExportInfo> {
+    let mut identities: HashMap<String, String> = HashMap::new();
+    let mut kept: HashMap<String, usize> = HashMap::new();
+    let mut unique: Vec<(String, ExportInfo)> = Vec::new();
+    for exp in exports {
+        let id = identities
+            .entry(exp.source.clone())
+            .or_insert_with(|| identity(&exp.source))
+            .clone();
+        let key = format!("{}:{}:{}:{}", &exp.export_type, exp.specifier, id, exp.is_type_export);
+        if let Some(&index) = kept.get(&key) {
+            prefer_spelling(&mut unique[index].1.source, &exp.source);
+        } else {
+            kept.insert(key, unique.len());
+            unique.push((id, exp));
+        }
+    }
+
+    merge_type_into_value(unique)
+}
+
+/// Drop type-only exports whose value counterpart is also exported
+/// A value re-export carries every meaning of the binding, including its type side
+fn merge_type_into_value(exports: Vec<(String, ExportInfo)>) -> Vec<ExportInfo> {
+    let values: HashSet<String> = exports
+        .iter()
+        .filter(|(_, exp)| !exp.is_type_export)
+        .map(|(id, exp)| format!("{}:{}:{}", &exp.export_type, exp.specifier, id))
+        .collect();
+
     exports
         .into_iter()
-        .filter(|exp| {
-            let key = format!(
-                "{}:{}:{}:{}",
-                &exp.export_type, exp.specifier, exp.source, exp.is_type_export
-            );
-            seen.insert(key)
+        .filter(|(id, exp)| {
+            !exp.is_type_export
+                || !values.contains(&format!("{}:{}:{}", &exp.export_type, exp.specifier, id))
         })
+        .map(|(_, exp)| exp)
         .collect()
 }
+
+/// Spell a merged source the shortest normalized way one of its duplicates was
+/// written, so `./Button/index.ts` and `./Button` become `./Button`
+fn prefer_spelling(kept: &mut String, duplicate: &str) {
+    if kept == duplicate {
+        return;
+    }
+    let (current, other) = (normalize_source(kept), normalize_source(duplicate));
+    *kept = if other.len() < current.len() {
+        other
+    } else {
+        current
+    };
+}
+
+/// Collapse `.` and `..` segments of a path source, keeping its `./` prefix
+/// Bare specifiers are returned unchanged
+#[must_use]
+pub fn normalize_source(source: &str) -> String {
+    if !is_relative_specifier(source) {
+        return source.to_string();
+    }
+    let normalized = normalize_path(Path::new(source))
+        .to_string_lossy()
+        .into_owned();
+    if normalized.is_empty() {
+        ".".to_string()
+    } else if normalized.starts_with('/') || normalized.starts_with("..") {
+        normalized
+    } else {
+        format!("./{normalized}")
+    }
+}
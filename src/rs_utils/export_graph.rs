@@ -0,0 +1,206 @@
+use super::barrel_loader::BarrelLoader;
+use super::file_system::{DependencyLog, FileSystem};
+use super::resolver::{normalize_path, ModuleInfo};
+use super::specifier::exported_name;
+use crate::types::{AffectedBarrel, BarrelLoaderOptions, ExportInfo};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+
+/// Long-lived reverse dependency graph of flattened barrels
+/// Answers which barrels and exported names a set of changed files affects, and
+/// re-resolves only the barrels whose re-export structure changed
+#[derive(Debug, Default)]
+pub struct ExportGraph {
+    options: BarrelLoaderOptions,
+    barrels: BTreeMap<PathBuf, BarrelNode>,
+    /// File or probed path to the barrels whose resolution read it
+    dependents: HashMap<PathBuf, BTreeSet<PathBuf>>,
+    /// Last known analysis of every module met while resolving
+    modules: HashMap<PathBuf, ModuleInfo>,
+}
+
+#[derive(Debug, Clone, Default)]
+struct BarrelNode {
+    /// Exported name, or `*` for star exports that stay as they are, to the
+    /// modules providing it
+    exports: BTreeMap<String, BTreeSet<PathBuf>>,
+    /// Files read and paths probed while resolving the barrel
+    dependencies: BTreeSet<PathBuf>,
+}
+
+impl ExportGraph {
+    /// Graph resolving barrels with the loader's resolution options
+    #[must_use]
+    pub fn new(options: BarrelLoaderOptions) -> Self {
+        Self { options, ..Self::default() }
+    }
+
+    /// Resolve a barrel and record its edges, replacing what was known about it
+    pub fn add_barrel<F: FileSystem>(&mut self, fs: &F, barrel: &Path) -> Result<(), String> {
+        let barrel = normalize_path(barrel);
+        let node = self.resolve(fs, &barrel)?;
+        self.insert(barrel, node);
+        Ok(())
+    }
+
+    /// Forget a barrel; returns whether it was tracked
+    pub fn remove_barrel(&mut self, barrel: &Path) -> bool {
+        let barrel = normalize_path(barrel);
+        let Some(node) = self.barrels.remove(&barrel) else {
+            return false;
+        };
+        self.unlink(&barrel, &node);
+        true
+    }
+
+    /// Tracked barrels, sorted by path
+    #[must_use]
+    pub fn barrels(&self) -> Vec<PathBuf> {
+        self.barrels.keys().cloned().collect()
+    }
+
+    /// Barrels whose resolution read or probed `file`
+    #[must_use]
+    pub fn dependents(&self, file: &Path) -> Vec<PathBuf> {
+        self.dependents
+            .get(&normalize_path(file))
+            .map(|barrels| barrels.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// Apply file changes and report the barrels whose exports changed
+    /// A name changes when it is added, removed, moves to another module or its
+    /// module is among the changed files. Barrels that no longer resolve report
+    /// every name they had and keep depending on their files
+    pub fn update<F: FileSystem>(&mut self, fs: &F, changed: &[PathBuf]) -> Vec<AffectedBarrel> {
+        let changed: BTreeSet<PathBuf> = changed.iter().map(|path| normalize_path(path)).collect();
+
+        let mut restructured: BTreeSet<PathBuf> = BTreeSet::new();
+        let mut touched: BTreeSet<PathBuf> = BTreeSet::new();
+        for file in &changed {
+            let Some(barrels) = self.dependents.get(file).cloned() else {
+                continue;
+            };
+            if self.shape_changed(fs, file) {
+                restructured.extend(barrels.iter().cloned());
+            }
+            touched.extend(barrels);
+        }
+
+        let mut affected = Vec::new();
+        for barrel in touched {
+            let names = if restructured.contains(&barrel) {
+                let before = self.barrels.remove(&barrel).unwrap_or_default();
+                self.unlink(&barrel, &before);
+                let after = self.resolve(fs, &barrel).unwrap_or_else(|_| BarrelNode {
+                    exports: BTreeMap::new(),
+                    dependencies: before.dependencies.clone(),
+                });
+                let names = changed_names(&before, &after, &changed);
+                self.insert(barrel.clone(), after);
+                names
+            } else {
+                let Some(node) = self.barrels.get(&barrel) else {
+                    continue;
+                };
+                changed_names(node, node, &changed)
+            };
+            if !names.is_empty() {
+                affected
+                    .push(AffectedBarrel { barrel: barrel.to_string_lossy().into_owned(), names });
+            }
+        }
+        affected
+    }
+
+    /// Whether a changed file's exports or declarations differ from the last
+    /// analysis; files that were never parsed as modules always count
+    fn shape_changed<F: FileSystem>(&mut self, fs: &F, file: &Path) -> bool {
+        let Some(previous) = self.modules.get(file) else {
+            return true;
+        };
+        let current = BarrelLoader::new(self.options.clone())
+            .with_file_system(fs)
+            .resolver(&file.to_string_lossy())
+            .and_then(|resolver| resolver.load(file));
+        match current {
+            Ok(module) if *module == *previous => false,
+            Ok(module) => {
+                self.modules.insert(file.to_path_buf(), (*module).clone());
+                true
+            }
+            Err(_) => {
+                self.modules.remove(file);
+                true
+            }
+        }
+    }
+
+    /// Flatten a barrel like the pipeline does, recording every path it touched
+    fn resolve<F: FileSystem>(&mut self, fs: &F, barrel: &Path) -> Result<BarrelNode, String> {
+        let log = DependencyLog::new();
+        let loader = BarrelLoader::new(self.options.clone()).with_file_system(log.track(fs));
+        let resolver = loader.resolver(&barrel.to_string_lossy())?;
+
+        let module = resolver.load(barrel)?;
+        let flattened: Vec<ExportInfo> = resolver
+            .resolve_exports(barrel, &module.exports)
+            .into_iter()
+            .map(|resolved| resolved.export)
+            .collect();
+        let mut exports: BTreeMap<String, BTreeSet<PathBuf>> = BTreeMap::new();
+        for exp in resolver.expand_namespace_exports(barrel, &flattened) {
+            let name = exported_name(&exp).unwrap_or("*").to_string();
+            let files = exports.entry(name).or_default();
+            files.extend(resolver.resolve_source(barrel, &exp.source));
+        }
+
+        let mut dependencies: BTreeSet<PathBuf> = BTreeSet::new();
+        for (path, module) in resolver.modules() {
+            self.modules.insert(path.clone(), (*module).clone());
+            dependencies.insert(path);
+        }
+        dependencies.extend(log.files());
+        dependencies.extend(log.missing());
+        Ok(BarrelNode { exports, dependencies })
+    }
+
+    fn insert(&mut self, barrel: PathBuf, node: BarrelNode) {
+        for dependency in &node.dependencies {
+            self.dependents
+                .entry(dependency.clone())
+                .or_default()
+                .insert(barrel.clone());
+        }
+        self.barrels.insert(barrel, node);
+    }
+
+    fn unlink(&mut self, barrel: &Path, node: &BarrelNode) {
+        for dependency in &node.dependencies {
+            if let Some(barrels) = self.dependents.get_mut(dependency) {
+                barrels.remove(barrel);
+                if barrels.is_empty() {
+                    self.dependents.remove(dependency);
+                }
+            }
+        }
+    }
+}
+
+/// Names added, removed, moved to other modules or provided by a changed file
+fn changed_names(
+    before: &BarrelNode,
+    after: &BarrelNode,
+    changed: &BTreeSet<PathBuf>,
+) -> Vec<String> {
+    let names: BTreeSet<&String> = before.exports.keys().chain(after.exports.keys()).collect();
+    names
+        .into_iter()
+        .filter(|name| {
+            let old = before.exports.get(*name);
+            let new = after.exports.get(*name);
+            old != new || new.is_some_and(|files| files.iter().any(|file| changed.contains(file)))
+        })
+        .cloned()
+        .collect()
+}
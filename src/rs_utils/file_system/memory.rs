@@ -0,0 +1,138 @@
+use super::super::resolver::normalize_path;
+use super::{FileSystem, Metadata};
+use std::collections::{BTreeMap, BTreeSet};
+use std::io;
+use std::ops::Bound;
+use std::path::{Path, PathBuf};
+
+/// Symlink hops followed before giving up, as `ELOOP` does on Linux
+const MAX_SYMLINK_DEPTH: usize = 40;
+
+/// In-memory file tree for tests and virtual module setups
+/// Directories exist implicitly above every file and symlink
+#[derive(Debug, Clone, Default)]
+pub struct MemoryFileSystem {
+    files: BTreeMap<PathBuf, String>,
+    /// Link path to target; relative targets are resolved from the link's directory
+    symlinks: BTreeMap<PathBuf, PathBuf>,
+}
+
+impl MemoryFileSystem {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a file, replacing any previous contents
+    #[must_use]
+    pub fn with_file(mut self, path: impl AsRef<Path>, contents: &str) -> Self {
+        self.insert_file(path, contents);
+        self
+    }
+
+    /// Add a symlink pointing at a file or directory
+    #[must_use]
+    pub fn with_symlink(mut self, link: impl AsRef<Path>, target: impl AsRef<Path>) -> Self {
+        self.symlinks
+            .insert(normalize_path(link.as_ref()), target.as_ref().to_path_buf());
+        self
+    }
+
+    pub fn insert_file(&mut self, path: impl AsRef<Path>, contents: &str) {
+        self.files
+            .insert(normalize_path(path.as_ref()), contents.to_string());
+    }
+
+    /// Follow symlinks in every component of `path`
+    fn resolve_links(&self, path: &Path) -> io::Result<PathBuf> {
+        let mut current = normalize_path(path);
+        for _ in 0..MAX_SYMLINK_DEPTH {
+            let Some((link, target)) = self
+                .symlinks
+                .iter()
+                .find(|(link, _)| current.starts_with(link))
+            else {
+                return Ok(current);
+            };
+            let rest = current.strip_prefix(link).unwrap_or_else(|_| Path::new(""));
+            let base = link.parent().unwrap_or_else(|| Path::new("")).join(target);
+            current = normalize_path(&base.join(rest));
+        }
+        Err(io::Error::other(format!(
+            "Too many levels of symbolic links: {}",
+            path.display()
+        )))
+    }
+
+    fn entries(&self) -> impl Iterator<Item = &PathBuf> {
+        self.files.keys().chain(self.symlinks.keys())
+    }
+
+    /// Whether any file or symlink lies below `dir`
+    /// Paths order by component, so descendants directly follow `dir` in each map
+    fn has_children(&self, dir: &Path) -> bool {
+        let first_after = |keys: Option<&PathBuf>| keys.is_some_and(|key| key.starts_with(dir));
+        let range = (Bound::Excluded(dir), Bound::Unbounded);
+        first_after(
+            self.files
+                .range::<Path, _>(range)
+                .next()
+                .map(|(key, _)| key),
+        ) || first_after(
+            self.symlinks
+                .range::<Path, _>(range)
+                .next()
+                .map(|(key, _)| key),
+        )
+    }
+}
+
+impl FileSystem for MemoryFileSystem {
+    fn read(&self, path: &Path) -> io::Result<String> {
+        let real = self.resolve_links(path)?;
+        self.files
+            .get(&real)
+            .cloned()
+            .ok_or_else(|| not_found(path))
+    }
+
+    fn stat(&self, path: &Path) -> io::Result<Metadata> {
+        let real = self.resolve_links(path)?;
+        let is_file = self.files.contains_key(&real);
+        let is_dir = !is_file && self.has_children(&real);
+        if !is_file && !is_dir {
+            return Err(not_found(path));
+        }
+        Ok(Metadata { is_file, is_dir, modified: None })
+    }
+
+    fn realpath(&self, path: &Path) -> io::Result<PathBuf> {
+        let real = self.resolve_links(path)?;
+        self.stat(&real)?;
+        Ok(real)
+    }
+
+    fn read_dir(&self, path: &Path) -> io::Result<Vec<PathBuf>> {
+        let real = self.resolve_links(path)?;
+        if !self.stat(&real)?.is_dir {
+            return Err(io::Error::other(format!("Not a directory: {}", path.display())));
+        }
+        let names: BTreeSet<&std::ffi::OsStr> = self
+            .entries()
+            .filter_map(|entry| entry.strip_prefix(&real).ok()?.iter().next())
+            .collect();
+        let dir = normalize_path(path);
+        Ok(names.into_iter().map(|name| dir.join(name)).collect())
+    }
+
+    fn as_sync(&self) -> Option<&(dyn FileSystem + Sync)> {
+        Some(self)
+    }
+}
+
+fn not_found(path: &Path) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::NotFound,
+        format!("No such file or directory: {}", path.display()),
+    )
+}
@@ -0,0 +1,81 @@
+mod memory;
+mod napi;
+mod os;
+mod tracking;
+
+pub use memory::MemoryFileSystem;
+pub use napi::NapiFileSystem;
+pub use os::OsFileSystem;
+pub use tracking::{DependencyLog, TrackingFileSystem};
+
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// What `stat` reports about a path, after following symlinks
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Metadata {
+    pub is_file: bool,
+    pub is_dir: bool,
+    /// Last modification time, when the filesystem tracks one
+    pub modified: Option<SystemTime>,
+}
+
+/// Filesystem access used by module resolution
+/// Implementations exist for the real disk, an in-memory tree for tests and
+/// webpack's `inputFileSystem` through NAPI, so virtual modules resolve too
+pub trait FileSystem {
+    /// Read a file as UTF-8 text
+    fn read(&self, path: &Path) -> io::Result<String>;
+
+    /// Describe a file or directory, following symlinks
+    fn stat(&self, path: &Path) -> io::Result<Metadata>;
+
+    /// Resolve symlinks and return the canonical path
+    fn realpath(&self, path: &Path) -> io::Result<PathBuf>;
+
+    /// List the entries of a directory as full paths
+    fn read_dir(&self, path: &Path) -> io::Result<Vec<PathBuf>>;
+
+    fn is_file(&self, path: &Path) -> bool {
+        self.stat(path).is_ok_and(|metadata| metadata.is_file)
+    }
+
+    fn is_dir(&self, path: &Path) -> bool {
+        self.stat(path).is_ok_and(|metadata| metadata.is_dir)
+    }
+
+    /// The filesystem as one that can be shared across threads, if it can
+    /// Modules are only read in parallel through filesystems that return `Some`
+    fn as_sync(&self) -> Option<&(dyn FileSystem + Sync)> {
+        None
+    }
+}
+
+impl<F: FileSystem + ?Sized> FileSystem for &F {
+    fn read(&self, path: &Path) -> io::Result<String> {
+        (**self).read(path)
+    }
+
+    fn stat(&self, path: &Path) -> io::Result<Metadata> {
+        (**self).stat(path)
+    }
+
+    fn realpath(&self, path: &Path) -> io::Result<PathBuf> {
+        (**self).realpath(path)
+    }
+
+    fn read_dir(&self, path: &Path) -> io::Result<Vec<PathBuf>> {
+        (**self).read_dir(path)
+    }
+
+    fn as_sync(&self) -> Option<&(dyn FileSystem + Sync)> {
+        (**self).as_sync()
+    }
+}
+
+/// Read a file, describing the path in the error
+pub fn read_file<F: FileSystem + ?Sized>(fs: &F, path: &Path) -> Result<String, String> {
+    fs.read(path)
+        .map_err(|e| format!("Failed to read {}: {e}", path.display()))
+}
@@ -0,0 +1,77 @@
+use super::{FileSystem, Metadata};
+use crate::types::FileStats;
+use napi::bindgen_prelude::{FromNapiValue, Function, JsObjectValue, Object};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, UNIX_EPOCH};
+
+/// Filesystem backed by JavaScript callbacks wrapping webpack's `inputFileSystem`
+/// Only usable during the NAPI call that received the adapter
+pub struct NapiFileSystem<'env> {
+    read_file: Function<'env, String, Option<String>>,
+    stat: Function<'env, String, Option<FileStats>>,
+    realpath: Function<'env, String, Option<String>>,
+    read_dir: Function<'env, String, Option<Vec<String>>>,
+}
+
+impl<'env> NapiFileSystem<'env> {
+    /// Wrap an adapter providing `readFile`, `stat`, `realpath` and `readDir`,
+    /// each returning `null` for a missing path
+    pub fn new(adapter: &Object<'env>) -> napi::Result<Self> {
+        Ok(Self {
+            read_file: adapter.get_named_property("readFile")?,
+            stat: adapter.get_named_property("stat")?,
+            realpath: adapter.get_named_property("realpath")?,
+            read_dir: adapter.get_named_property("readDir")?,
+        })
+    }
+}
+
+impl FileSystem for NapiFileSystem<'_> {
+    fn read(&self, path: &Path) -> io::Result<String> {
+        call(&self.read_file, path)
+    }
+
+    fn stat(&self, path: &Path) -> io::Result<Metadata> {
+        let stats = call(&self.stat, path)?;
+        Ok(Metadata {
+            is_file: stats.is_file,
+            is_dir: stats.is_directory,
+            modified: stats
+                .mtime_ms
+                .and_then(|ms| Duration::try_from_secs_f64(ms / 1000.0).ok())
+                .map(|elapsed| UNIX_EPOCH + elapsed),
+        })
+    }
+
+    fn realpath(&self, path: &Path) -> io::Result<PathBuf> {
+        call(&self.realpath, path).map(PathBuf::from)
+    }
+
+    fn read_dir(&self, path: &Path) -> io::Result<Vec<PathBuf>> {
+        let names = call(&self.read_dir, path)?;
+        Ok(names.into_iter().map(|name| path.join(name)).collect())
+    }
+}
+
+impl std::fmt::Debug for NapiFileSystem<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("NapiFileSystem").finish_non_exhaustive()
+    }
+}
+
+/// Call an adapter function with a path; `null` means the path does not exist
+fn call<T: FromNapiValue>(
+    function: &Function<'_, String, Option<T>>,
+    path: &Path,
+) -> io::Result<T> {
+    function
+        .call(path.to_string_lossy().into_owned())
+        .map_err(|e| io::Error::other(e.reason.clone()))?
+        .ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("No such file or directory: {}", path.display()),
+            )
+        })
+}
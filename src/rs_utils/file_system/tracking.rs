@@ -0,0 +1,143 @@
+use super::{FileSystem, Metadata};
+use std::collections::BTreeSet;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// Paths module resolution depended on, as webpack's watch mode needs them
+#[derive(Debug, Default)]
+pub struct DependencyLog {
+    files: Mutex<BTreeSet<PathBuf>>,
+    contexts: Mutex<BTreeSet<PathBuf>>,
+    missing: Mutex<BTreeSet<PathBuf>>,
+}
+
+impl DependencyLog {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Wrap `fs` so everything read or probed through it is recorded here
+    pub fn track<'a>(&'a self, fs: &'a dyn FileSystem) -> TrackingFileSystem<'a> {
+        TrackingFileSystem {
+            local: Tracked { inner: fs, log: self },
+            shared: fs.as_sync().map(|inner| Tracked { inner, log: self }),
+        }
+    }
+
+    /// Files that were read or found while probing
+    #[must_use]
+    pub fn files(&self) -> Vec<PathBuf> {
+        snapshot(&self.files)
+    }
+
+    /// Directories whose entries were listed, or where probing missed some paths
+    /// before finding a file; their contents decide which module a source is
+    #[must_use]
+    pub fn contexts(&self) -> Vec<PathBuf> {
+        let files = self.files();
+        let found: BTreeSet<&Path> = files.iter().filter_map(|file| file.parent()).collect();
+        let mut contexts: BTreeSet<PathBuf> = snapshot(&self.contexts).into_iter().collect();
+        contexts.extend(
+            self.missing()
+                .iter()
+                .filter_map(|path| path.parent())
+                .filter(|dir| found.contains(dir))
+                .map(Path::to_path_buf),
+        );
+        contexts.into_iter().collect()
+    }
+
+    /// Paths that were probed and not found; creating one changes the result
+    #[must_use]
+    pub fn missing(&self) -> Vec<PathBuf> {
+        snapshot(&self.missing)
+    }
+
+    fn record<T>(&self, path: &Path, result: &io::Result<T>, found: impl FnOnce(&T) -> bool) {
+        let set = match result {
+            Ok(value) if found(value) => &self.files,
+            Ok(_) => return,
+            Err(_) => &self.missing,
+        };
+        if let Ok(mut set) = set.lock() {
+            set.insert(path.to_path_buf());
+        }
+    }
+}
+
+/// Filesystem recording its reads and failed probes into a `DependencyLog`
+/// Shareable across threads whenever the wrapped filesystem is
+pub struct TrackingFileSystem<'a> {
+    local: Tracked<'a, dyn FileSystem + 'a>,
+    shared: Option<Tracked<'a, dyn FileSystem + Sync + 'a>>,
+}
+
+impl FileSystem for TrackingFileSystem<'_> {
+    fn read(&self, path: &Path) -> io::Result<String> {
+        self.local.read(path)
+    }
+
+    fn stat(&self, path: &Path) -> io::Result<Metadata> {
+        self.local.stat(path)
+    }
+
+    fn realpath(&self, path: &Path) -> io::Result<PathBuf> {
+        self.local.realpath(path)
+    }
+
+    fn read_dir(&self, path: &Path) -> io::Result<Vec<PathBuf>> {
+        self.local.read_dir(path)
+    }
+
+    fn as_sync(&self) -> Option<&(dyn FileSystem + Sync)> {
+        self.shared
+            .as_ref()
+            .map(|shared| shared as &(dyn FileSystem + Sync))
+    }
+}
+
+struct Tracked<'a, F: ?Sized> {
+    inner: &'a F,
+    log: &'a DependencyLog,
+}
+
+impl<F: FileSystem + ?Sized> FileSystem for Tracked<'_, F> {
+    fn read(&self, path: &Path) -> io::Result<String> {
+        let result = self.inner.read(path);
+        self.log.record(path, &result, |_| true);
+        result
+    }
+
+    fn stat(&self, path: &Path) -> io::Result<Metadata> {
+        let result = self.inner.stat(path);
+        self.log.record(path, &result, |metadata| metadata.is_file);
+        result
+    }
+
+    fn realpath(&self, path: &Path) -> io::Result<PathBuf> {
+        let result = self.inner.realpath(path);
+        self.log.record(path, &result, |_| false);
+        result
+    }
+
+    fn read_dir(&self, path: &Path) -> io::Result<Vec<PathBuf>> {
+        let result = self.inner.read_dir(path);
+        match &result {
+            Ok(_) => {
+                if let Ok(mut contexts) = self.log.contexts.lock() {
+                    contexts.insert(path.to_path_buf());
+                }
+            }
+            Err(_) => self.log.record(path, &result, |_| false),
+        }
+        result
+    }
+}
+
+fn snapshot(set: &Mutex<BTreeSet<PathBuf>>) -> Vec<PathBuf> {
+    set.lock()
+        .map(|set| set.iter().cloned().collect())
+        .unwrap_or_default()
+}
@@ -0,0 +1,140 @@
+use super::file_system::{read_file, FileSystem};
+use super::resolver::normalize_path;
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+
+/// Specifier schemes that are never mapped to files
+const URL_SCHEMES: &[&str] = &["http:", "https:", "npm:", "jsr:", "node:", "data:", "file:"];
+
+/// Where an import map sends a specifier
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MappedSpecifier {
+    /// A path on disk, still subject to extension and index probing
+    Path(PathBuf),
+    /// A URL or registry specifier that must be left untouched
+    Url(String),
+}
+
+/// Import map (`import_map.json`) with top-level `imports` and per-directory `scopes`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ImportMap {
+    /// Specifier map, keys ending in `/` match by prefix
+    pub imports: Vec<(String, MappedSpecifier)>,
+    /// Scope directory and the specifier map that applies inside it
+    pub scopes: Vec<(PathBuf, Vec<(String, MappedSpecifier)>)>,
+}
+
+impl ImportMap {
+    /// Load an import map; paths in it are relative to the map file
+    pub fn load<F: FileSystem>(fs: &F, path: &Path) -> Result<Self, String> {
+        let source = read_file(fs, path)?;
+        let json: Value = serde_json::from_str(&source)
+            .map_err(|e| format!("Failed to parse {}: {e}", path.display()))?;
+        let base = path.parent().unwrap_or_else(|| Path::new(""));
+
+        let scopes = json
+            .get("scopes")
+            .and_then(Value::as_object)
+            .map(|scopes| {
+                scopes
+                    .iter()
+                    .map(|(scope, map)| (normalize_path(&base.join(scope)), parse_map(map, base)))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Ok(Self {
+            imports: json
+                .get("imports")
+                .map(|map| parse_map(map, base))
+                .unwrap_or_default(),
+            scopes,
+        })
+    }
+
+    /// Map a specifier used by `importer`
+    /// The most specific scope containing the importer is tried before top-level imports
+    #[must_use]
+    pub fn resolve(&self, importer: &Path, specifier: &str) -> Option<MappedSpecifier> {
+        let key = if is_path_like(specifier) {
+            let dir = importer.parent().unwrap_or_else(|| Path::new(""));
+            normalize_path(&dir.join(specifier))
+                .to_string_lossy()
+                .into_owned()
+        } else {
+            specifier.to_string()
+        };
+
+        let mut scopes: Vec<&(PathBuf, Vec<(String, MappedSpecifier)>)> = self
+            .scopes
+            .iter()
+            .filter(|(scope, _)| importer.starts_with(scope))
+            .collect();
+        scopes.sort_by_key(|(scope, _)| std::cmp::Reverse(scope.components().count()));
+
+        scopes
+            .into_iter()
+            .map(|(_, map)| map)
+            .chain(std::iter::once(&self.imports))
+            .find_map(|map| match_specifier(map, &key))
+    }
+}
+
+/// Check if a specifier is a URL or registry specifier
+#[must_use]
+pub fn is_url_specifier(specifier: &str) -> bool {
+    URL_SCHEMES
+        .iter()
+        .any(|scheme| specifier.starts_with(scheme))
+}
+
+/// Exact keys win; otherwise the longest `/`-terminated prefix applies
+fn match_specifier(map: &[(String, MappedSpecifier)], key: &str) -> Option<MappedSpecifier> {
+    if let Some((_, target)) = map.iter().find(|(k, _)| k == key) {
+        return Some(target.clone());
+    }
+
+    let (prefix, target) = map
+        .iter()
+        .filter(|(k, _)| k.ends_with('/') && key.starts_with(k.as_str()))
+        .max_by_key(|(k, _)| k.len())?;
+    let rest = &key[prefix.len()..];
+    Some(match target {
+        MappedSpecifier::Path(path) => MappedSpecifier::Path(path.join(rest)),
+        MappedSpecifier::Url(url) => MappedSpecifier::Url(format!("{url}{rest}")),
+    })
+}
+
+/// Parse a specifier map, resolving path keys and targets against the map's directory
+fn parse_map(map: &Value, base: &Path) -> Vec<(String, MappedSpecifier)> {
+    let Some(entries) = map.as_object() else {
+        return Vec::new();
+    };
+    entries
+        .iter()
+        .filter_map(|(key, target)| {
+            let target = target.as_str()?;
+            let key = if is_path_like(key) {
+                let mut path = normalize_path(&base.join(key))
+                    .to_string_lossy()
+                    .into_owned();
+                if key.ends_with('/') {
+                    path.push('/');
+                }
+                path
+            } else {
+                key.clone()
+            };
+            let target = if is_path_like(target) {
+                MappedSpecifier::Path(normalize_path(&base.join(target)))
+            } else {
+                MappedSpecifier::Url(target.to_string())
+            };
+            Some((key, target))
+        })
+        .collect()
+}
+
+fn is_path_like(value: &str) -> bool {
+    value.starts_with("./") || value.starts_with("../") || value.starts_with('/')
+}
@@ -0,0 +1,255 @@
+use super::file_system::FileSystem;
+use super::parser::{parse_imports, ImportStatement, ImportedName};
+use super::resolver::{is_relative_specifier, ResolvedExport, Resolver, SourceStyle};
+use super::specifier::split_specifier;
+use std::path::{Component, Path, PathBuf};
+
+/// Where one imported binding comes from after bypassing the barrel
+struct Target {
+    source: String,
+    /// Name the module declares it under; `default` for default exports
+    imported: String,
+    is_namespace: bool,
+}
+
+/// Bindings imported from one module
+#[derive(Default)]
+struct Group {
+    source: String,
+    names: Vec<ImportedName>,
+    namespace: Option<String>,
+}
+
+/// Rewrite imports from barrels so every name is imported from the module that
+/// declares it, like Next.js `optimizePackageImports`
+/// Names that cannot be traced safely or would skip a module with side effects stay
+/// on the barrel import, and aliased or package specifiers are only replaced by
+/// specifiers that resolve back
+#[must_use]
+pub fn rewrite_imports<F: FileSystem>(
+    resolver: &Resolver<F>,
+    source: &str,
+    importer: &Path,
+    style: SourceStyle,
+) -> String {
+    let mut output = String::with_capacity(source.len());
+    let mut last = 0;
+    for statement in parse_imports(source) {
+        let Some(replacement) = rewrite_statement(resolver, &statement, importer, style) else {
+            continue;
+        };
+        output.push_str(&source[last..statement.range.start]);
+        output.push_str(&replacement);
+        last = statement.range.end;
+    }
+    output.push_str(&source[last..]);
+    output
+}
+
+/// Statements replacing one import, or `None` when it stays as written
+fn rewrite_statement<F: FileSystem>(
+    resolver: &Resolver<F>,
+    statement: &ImportStatement,
+    importer: &Path,
+    style: SourceStyle,
+) -> Option<String> {
+    if statement.namespace.is_some() {
+        return None;
+    }
+    let barrel = resolver.resolve_source(importer, &statement.source)?;
+    if !resolver
+        .load(&barrel)
+        .is_ok_and(|module| module.is_pure_barrel)
+    {
+        return None;
+    }
+
+    let (groups, kept) = group_by_target(resolver, statement, importer, &barrel, style);
+    if groups.is_empty() {
+        return None;
+    }
+
+    let mut lines: Vec<String> = Vec::new();
+    for group in &groups {
+        if !group.names.is_empty() {
+            lines.push(format_import(statement, &group.names, None, &group.source));
+        }
+        if let Some(namespace) = &group.namespace {
+            lines.push(format_import(statement, &[], Some(namespace), &group.source));
+        }
+    }
+    if !kept.is_empty() {
+        lines.push(format_import(statement, &kept, None, &statement.source));
+    }
+    Some(lines.join("\n"))
+}
+
+/// Bindings of one statement grouped by the module they come from, in
+/// first-seen order, and the bindings that stay on the barrel import
+fn group_by_target<F: FileSystem>(
+    resolver: &Resolver<F>,
+    statement: &ImportStatement,
+    importer: &Path,
+    barrel: &Path,
+    style: SourceStyle,
+) -> (Vec<Group>, Vec<ImportedName>) {
+    let mut groups: Vec<Group> = Vec::new();
+    let mut kept: Vec<ImportedName> = Vec::new();
+    for name in &statement.names {
+        let Some(target) = resolver
+            .trace_export(barrel, &name.imported)
+            .and_then(|found| target(resolver, importer, &statement.source, barrel, found, style))
+        else {
+            kept.push(name.clone());
+            continue;
+        };
+        let index = groups
+            .iter()
+            .position(|group| group.source == target.source)
+            .unwrap_or_else(|| {
+                groups.push(Group { source: target.source.clone(), ..Group::default() });
+                groups.len() - 1
+            });
+        let group = &mut groups[index];
+        if !target.is_namespace {
+            group
+                .names
+                .push(ImportedName { imported: target.imported, ..name.clone() });
+        } else if group.namespace.is_none() {
+            group.namespace = Some(name.local.clone());
+        } else {
+            kept.push(name.clone());
+        }
+    }
+    (groups, kept)
+}
+
+/// Where a traced binding should be imported from, if that is safe
+fn target<F: FileSystem>(
+    resolver: &Resolver<F>,
+    importer: &Path,
+    specifier: &str,
+    barrel: &Path,
+    found: ResolvedExport,
+    style: SourceStyle,
+) -> Option<Target> {
+    let file = found.file?;
+    if resolver.canonical(&file) == resolver.canonical(barrel)
+        || found
+            .via
+            .iter()
+            .any(|module| resolver.has_side_effects(module))
+    {
+        return None;
+    }
+    let (binding, is_namespace) = match found.export.export_type.as_str() {
+        "namespace" => (String::new(), true),
+        "default" => ("default".to_string(), false),
+        _ => (split_specifier(&found.export.specifier).0.to_string(), false),
+    };
+    Some(Target {
+        source: leaf_specifier(resolver, importer, specifier, &file, style)?,
+        imported: binding,
+        is_namespace,
+    })
+}
+
+/// Specifier for `file` in the form the original import used: relative imports
+/// follow `style`, aliases prefer tsconfig `paths` and packages an exposed subpath
+fn leaf_specifier<F: FileSystem>(
+    resolver: &Resolver<F>,
+    importer: &Path,
+    specifier: &str,
+    file: &Path,
+    style: SourceStyle,
+) -> Option<String> {
+    if is_relative_specifier(specifier) {
+        return Some(resolver.source_specifier(importer, file, style));
+    }
+    // Files inside a package are only reachable through subpaths it exposes
+    if let Some(subpaths) = package_subpaths(file) {
+        let target = resolver.canonical(file);
+        return subpaths.into_iter().find(|candidate| {
+            resolver
+                .resolve_source(importer, candidate)
+                .is_some_and(|resolved| resolver.canonical(&resolved) == target)
+        });
+    }
+    Some(resolver.source_specifier(importer, file, SourceStyle::Alias))
+}
+
+/// Bare specifiers reaching a file inside `node_modules`, with and without its
+/// extension, such as `@acme/ui/dist/button`
+fn package_subpaths(file: &Path) -> Option<Vec<String>> {
+    let components: Vec<Component> = file.components().collect();
+    let start = components
+        .iter()
+        .rposition(|component| component.as_os_str() == "node_modules")?
+        + 1;
+    let parts: Vec<String> = components[start..]
+        .iter()
+        .map(|component| component.as_os_str().to_string_lossy().into_owned())
+        .collect();
+    let name_len = if parts.first()?.starts_with('@') {
+        2
+    } else {
+        1
+    };
+    if parts.len() <= name_len {
+        return None;
+    }
+
+    let full = parts.join("/");
+    let stem = PathBuf::from(&full)
+        .with_extension("")
+        .to_string_lossy()
+        .into_owned();
+    Some(vec![stem, full])
+}
+
+/// Write an import statement in the layout of the one it replaces
+fn format_import(
+    statement: &ImportStatement,
+    names: &[ImportedName],
+    namespace: Option<&String>,
+    source: &str,
+) -> String {
+    let default = names
+        .iter()
+        .find(|name| name.imported == "default" && !name.is_type);
+    let mut clauses: Vec<String> = Vec::new();
+    if let Some(default) = default {
+        clauses.push(default.local.clone());
+    }
+    if let Some(namespace) = namespace {
+        clauses.push(format!("* as {namespace}"));
+    }
+    let braced: Vec<String> = names
+        .iter()
+        .filter(|name| !default.is_some_and(|default| std::ptr::eq(*name, default)))
+        .map(|name| {
+            let modifier = if name.is_type && !statement.is_type {
+                "type "
+            } else {
+                ""
+            };
+            if name.imported == name.local {
+                format!("{modifier}{}", name.local)
+            } else {
+                format!("{modifier}{} as {}", name.imported, name.local)
+            }
+        })
+        .collect();
+    if !braced.is_empty() {
+        clauses.push(format!("{{ {} }}", braced.join(", ")));
+    }
+
+    format!(
+        "{indent}import {kind}{clause} from {quote}{source}{quote}{semicolon}",
+        indent = statement.indent,
+        kind = if statement.is_type { "type " } else { "" },
+        clause = clauses.join(", "),
+        quote = statement.quote,
+        semicolon = if statement.semicolon { ";" } else { "" },
+    )
+}
@@ -0,0 +1,116 @@
+use super::file_system::FileSystem;
+use super::parser::{parse_exports, parse_local_exports};
+use super::resolver::{ResolvedExport, Resolver};
+use super::specifier::{exported_name, split_specifier};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// Version of the manifest format, bumped on incompatible changes
+/// The format is described by `docs/manifest.schema.json`
+pub const MANIFEST_VERSION: u32 = 1;
+
+/// Where every name a barrel exports comes from
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExportManifest {
+    pub version: u32,
+    pub barrel: String,
+    /// Exported names, sorted; the first statement providing a name wins
+    pub exports: BTreeMap<String, ManifestEntry>,
+    /// `export *` statements whose names could not be determined
+    pub stars: Vec<ManifestStar>,
+}
+
+/// Origin of one exported name
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ManifestEntry {
+    /// Module declaring the binding, or `None` when the source does not resolve
+    pub file: Option<String>,
+    /// Name the module declares it under: `default` for default exports and
+    /// `*` for namespace re-exports
+    pub local: String,
+    /// `value` or `type`
+    pub kind: String,
+    /// Barrels the name was re-exported through, outermost first
+    pub via: Vec<String>,
+}
+
+/// A star export kept as written
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ManifestStar {
+    pub source: String,
+    pub file: Option<String>,
+}
+
+/// Trace every name `source` exports to the module declaring it
+/// The resolver should not stop at side effects, so origins are the declarations
+pub fn build_manifest<F: FileSystem>(
+    resolver: &Resolver<F>,
+    source: &str,
+    barrel: &Path,
+) -> Result<ExportManifest, String> {
+    let exports = parse_exports(source)?;
+    let expanded = resolver.expand_namespace_exports(barrel, &exports);
+
+    let mut manifest = ExportManifest {
+        version: MANIFEST_VERSION,
+        barrel: barrel.to_string_lossy().into_owned(),
+        exports: BTreeMap::new(),
+        stars: Vec::new(),
+    };
+    for local in parse_local_exports(source) {
+        manifest
+            .exports
+            .entry(local.name.clone())
+            .or_insert_with(|| ManifestEntry {
+                file: Some(manifest.barrel.clone()),
+                local: local.name,
+                kind: kind(local.is_type).to_string(),
+                via: Vec::new(),
+            });
+    }
+    for resolved in resolver.resolve_exports(barrel, &expanded) {
+        let file = resolved
+            .file
+            .as_ref()
+            .map(|file| file.to_string_lossy().into_owned());
+        match exported_name(&resolved.export) {
+            Some(name) => {
+                manifest
+                    .exports
+                    .entry(name.to_string())
+                    .or_insert_with(|| entry(&resolved, file));
+            }
+            None => manifest
+                .stars
+                .push(ManifestStar { source: resolved.export.source, file }),
+        }
+    }
+    Ok(manifest)
+}
+
+fn entry(resolved: &ResolvedExport, file: Option<String>) -> ManifestEntry {
+    let local = match resolved.export.export_type.as_str() {
+        "namespace" => "*",
+        "default" => "default",
+        _ => split_specifier(&resolved.export.specifier).0,
+    };
+    ManifestEntry {
+        file,
+        local: local.to_string(),
+        kind: kind(resolved.export.is_type_export).to_string(),
+        via: resolved
+            .via
+            .iter()
+            .map(|path| path.to_string_lossy().into_owned())
+            .collect(),
+    }
+}
+
+const fn kind(is_type: bool) -> &'static str {
+    if is_type {
+        "type"
+    } else {
+        "value"
+    }
+}
@@ -1,6 +1,18 @@
 pub mod barrel_loader;
+pub mod conflicts;
 pub mod deduplication;
+pub mod export_graph;
+pub mod file_system;
+pub mod import_map;
+pub mod import_rewrite;
+pub mod manifest;
+pub mod module_cache;
 pub mod napi_bindings;
+pub mod package_json;
 pub mod parser;
+pub mod pruning;
 pub mod reconstruction;
+pub mod resolver;
 pub mod sorting;
+pub mod specifier;
+pub mod tsconfig;
@@ -0,0 +1,238 @@
+use super::resolver::ModuleInfo;
+use crate::types::CacheStats;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, LazyLock, Mutex};
+use std::time::SystemTime;
+
+/// Part of every key, so entries written by another loader version are never read
+/// Bump the suffix when the analysis or the entry format changes
+const CACHE_VERSION: &str = concat!(env!("CARGO_PKG_VERSION"), "+5");
+
+/// Size limit of a cache directory unless configured otherwise
+pub const DEFAULT_MAX_SIZE: u64 = 256 * 1024 * 1024;
+
+/// Counters shared by every cache on the same directory in this process
+static COUNTERS: LazyLock<Mutex<HashMap<PathBuf, Arc<Counters>>>> = LazyLock::new(Mutex::default);
+
+/// Makes temporary file names unique between threads of one process
+static NEXT_TEMP: AtomicUsize = AtomicUsize::new(0);
+
+/// Analysis stored for one file content, including the parse error if it failed
+/// The file name only carries a 64-bit hash, so the entry records the length and a
+/// 128-bit hash of the contents it was written for
+#[derive(Serialize, Deserialize)]
+struct Entry {
+    version: String,
+    length: usize,
+    checksum: String,
+    analysis: Result<ModuleInfo, String>,
+}
+
+#[derive(Debug, Default)]
+struct Counters {
+    hits: AtomicU64,
+    misses: AtomicU64,
+    writes: AtomicU64,
+    evictions: AtomicU64,
+    /// Bytes written since the directory size was last checked
+    unchecked_bytes: AtomicU64,
+}
+
+/// Parse results stored on disk, keyed by content hash and loader version
+/// Warm builds read unchanged files back instead of parsing them again
+#[derive(Debug, Clone)]
+pub struct ModuleCache {
+    dir: PathBuf,
+    max_size: u64,
+    counters: Arc<Counters>,
+}
+
+impl ModuleCache {
+    #[must_use]
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        let dir = dir.into();
+        let counters = COUNTERS
+            .lock()
+            .map(|mut counters| Arc::clone(counters.entry(dir.clone()).or_default()))
+            .unwrap_or_default();
+        Self { dir, max_size: DEFAULT_MAX_SIZE, counters }
+    }
+
+    /// Evict least recently used entries once the directory grows past `bytes`
+    #[must_use]
+    pub const fn with_max_size(mut self, bytes: u64) -> Self {
+        self.max_size = bytes;
+        self
+    }
+
+    /// Return the stored analysis of `source`, or run `analyze` and store its result
+    /// Cache failures never fail the build; the file is then simply parsed
+    pub fn analyze(
+        &self,
+        source: &str,
+        analyze: impl FnOnce(&str) -> Result<ModuleInfo, String>,
+    ) -> Result<ModuleInfo, String> {
+        let path = self.entry_path(source);
+        if let Some(analysis) = read_entry(&path, source) {
+            self.counters.hits.fetch_add(1, Ordering::Relaxed);
+            return analysis;
+        }
+
+        self.counters.misses.fetch_add(1, Ordering::Relaxed);
+        let analysis = analyze(source);
+        if let Ok(bytes) = self.write_entry(&path, source, &analysis) {
+            self.counters.writes.fetch_add(1, Ordering::Relaxed);
+            self.after_write(bytes);
+        }
+        analysis
+    }
+
+    /// Remove least recently used entries until the directory fits the size limit
+    /// Returns how many entries were removed
+    pub fn evict(&self) -> io::Result<usize> {
+        let mut entries = self.entries()?;
+        let mut total: u64 = entries.iter().map(|(_, size, _)| size).sum();
+        entries.sort_by_key(|(_, _, modified)| *modified);
+
+        let mut evicted = 0;
+        for (path, size, _) in entries {
+            if total <= self.max_size {
+                break;
+            }
+            // Another process may have evicted it already
+            if fs::remove_file(&path).is_ok() {
+                evicted += 1;
+            }
+            total = total.saturating_sub(size);
+        }
+        self.counters
+            .evictions
+            .fetch_add(evicted as u64, Ordering::Relaxed);
+        Ok(evicted)
+    }
+
+    /// Hits, misses, writes and evictions since the process started, and the
+    /// entries currently stored in the directory
+    #[must_use]
+    pub fn stats(&self) -> CacheStats {
+        let entries = self.entries().unwrap_or_default();
+        let count = |counter: &AtomicU64| {
+            u32::try_from(counter.load(Ordering::Relaxed)).unwrap_or(u32::MAX)
+        };
+        CacheStats {
+            hits: count(&self.counters.hits),
+            misses: count(&self.counters.misses),
+            writes: count(&self.counters.writes),
+            evictions: count(&self.counters.evictions),
+            entries: u32::try_from(entries.len()).unwrap_or(u32::MAX),
+            #[allow(clippy::cast_precision_loss)]
+            bytes: entries.iter().map(|(_, size, _)| size).sum::<u64>() as f64,
+        }
+    }
+
+    fn entry_path(&self, source: &str) -> PathBuf {
+        let hash = fnv1a(&[CACHE_VERSION.as_bytes(), source.as_bytes()]);
+        self.dir.join(format!("{hash:016x}.json"))
+    }
+
+    /// Write through a temporary file and rename it into place, so readers in
+    /// other threads or processes never see a partial entry
+    fn write_entry(
+        &self,
+        path: &Path,
+        source: &str,
+        analysis: &Result<ModuleInfo, String>,
+    ) -> io::Result<u64> {
+        let entry = Entry {
+            version: CACHE_VERSION.to_string(),
+            length: source.len(),
+            checksum: checksum(source),
+            analysis: analysis.clone(),
+        };
+        let contents = serde_json::to_vec(&entry).map_err(io::Error::other)?;
+        fs::create_dir_all(&self.dir)?;
+
+        let temp = self.dir.join(format!(
+            ".{}-{}.tmp",
+            std::process::id(),
+            NEXT_TEMP.fetch_add(1, Ordering::Relaxed)
+        ));
+        fs::write(&temp, &contents)?;
+        fs::rename(&temp, path).inspect_err(|_| {
+            let _ = fs::remove_file(&temp);
+        })?;
+        Ok(contents.len() as u64)
+    }
+
+    /// Check the directory size after roughly a tenth of the limit was written
+    fn after_write(&self, bytes: u64) {
+        let unchecked = self
+            .counters
+            .unchecked_bytes
+            .fetch_add(bytes, Ordering::Relaxed)
+            + bytes;
+        if unchecked >= self.max_size / 10 {
+            self.counters.unchecked_bytes.store(0, Ordering::Relaxed);
+            let _ = self.evict();
+        }
+    }
+
+    /// Stored entries with their size and last use
+    fn entries(&self) -> io::Result<Vec<(PathBuf, u64, SystemTime)>> {
+        let mut entries = Vec::new();
+        for entry in fs::read_dir(&self.dir)? {
+            let path = entry?.path();
+            if path.extension().is_none_or(|ext| ext != "json") {
+                continue;
+            }
+            if let Ok(metadata) = fs::metadata(&path) {
+                let modified = metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH);
+                entries.push((path, metadata.len(), modified));
+            }
+        }
+        Ok(entries)
+    }
+}
+
+/// Read an entry written by this loader version for exactly `source`, marking it
+/// as recently used; entries of other contents sharing the file name are ignored
+fn read_entry(path: &Path, source: &str) -> Option<Result<ModuleInfo, String>> {
+    let contents = fs::read(path).ok()?;
+    let entry: Entry = serde_json::from_slice(&contents).ok()?;
+    if entry.version != CACHE_VERSION
+        || entry.length != source.len()
+        || entry.checksum != checksum(source)
+    {
+        return None;
+    }
+    let _ = File::options()
+        .append(true)
+        .open(path)
+        .and_then(|file| file.set_modified(SystemTime::now()));
+    Some(entry.analysis)
+}
+
+/// 64-bit FNV-1a over several byte strings
+fn fnv1a(parts: &[&[u8]]) -> u64 {
+    const OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+    parts
+        .iter()
+        .flat_map(|part| part.iter())
+        .fold(OFFSET, |hash, byte| (hash ^ u64::from(*byte)).wrapping_mul(PRIME))
+}
+
+/// 128-bit FNV-1a of the contents, independent of the 64-bit file name hash
+fn checksum(source: &str) -> String {
+    const OFFSET: u128 = 0x6c62_272e_07bb_0142_62b8_2175_6295_c58d;
+    const PRIME: u128 = 0x0000_0000_0100_0000_0000_0000_0000_013b;
+    let hash = source
+        .bytes()
+        .fold(OFFSET, |hash, byte| (hash ^ u128::from(byte)).wrapping_mul(PRIME));
+    format!("{hash:032x}")
+}
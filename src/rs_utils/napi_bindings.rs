@@ -1,11 +1,22 @@
 use super::barrel_loader::BarrelLoader;
+use super::conflicts::detect_conflicts as detect_conflicts_internal;
 use super::deduplication::remove_duplicates as remove_duplicates_internal;
+use super::export_graph::ExportGraph;
+use super::file_system::{FileSystem, NapiFileSystem, OsFileSystem};
+use super::module_cache::ModuleCache;
 use super::parser::parse_exports as parse_exports_internal;
 use super::reconstruction::reconstruct_source as reconstruct_source_internal;
-use super::sorting::sort_exports as sort_exports_internal;
-use crate::types::{BarrelLoaderOptions, ExportInfo};
+use super::sorting::{
+    sort_exports as sort_exports_internal, sort_exports_by as sort_exports_by_internal,
+    SortStrategy,
+};
+use crate::types::{
+    AffectedBarrel, BarrelLoaderOptions, CacheStats, ExportConflict, ExportCycle, ExportInfo,
+    ModuleSideEffects, ProcessResult,
+};
 use napi::bindgen_prelude::*;
 use napi_derive::napi;
+use std::path::{Path, PathBuf};
 
 #[napi]
 #[allow(clippy::needless_pass_by_value)]
@@ -13,14 +24,53 @@ pub fn process_barrel_file(
     source: String,
     file_path: String,
     options: Option<BarrelLoaderOptions>,
+    file_system: Option<Object>,
 ) -> Result<String> {
-    let opts: BarrelLoaderOptions = options.unwrap_or_default();
-    let loader: BarrelLoader = BarrelLoader::new(opts);
-    loader
-        .process(&source, &file_path)
+    with_loader(options, file_system.as_ref(), |loader| loader.process(&source, &file_path))
         .map_err(|e: String| napi::Error::new(napi::Status::GenericFailure, e))
 }
 
+#[napi]
+#[allow(clippy::needless_pass_by_value)]
+pub fn process_with_dependencies_napi(
+    source: String,
+    file_path: String,
+    options: Option<BarrelLoaderOptions>,
+    file_system: Option<Object>,
+) -> Result<ProcessResult> {
+    with_loader(options, file_system.as_ref(), |loader| {
+        loader.process_with_dependencies(&source, &file_path)
+    })
+    .map_err(|e: String| napi::Error::new(napi::Status::GenericFailure, e))
+}
+
+/// Run with a loader reading through the JavaScript filesystem adapter when one
+/// is given (see `createNativeFileSystem`), or the disk otherwise
+fn with_loader<T>(
+    options: Option<BarrelLoaderOptions>,
+    file_system: Option<&Object>,
+    run: impl FnOnce(&BarrelLoader<&dyn FileSystem>) -> std::result::Result<T, String>,
+) -> std::result::Result<T, String> {
+    with_file_system(file_system, |fs| {
+        run(&BarrelLoader::new(options.unwrap_or_default()).with_file_system(fs))
+    })
+}
+
+fn with_file_system<T>(
+    file_system: Option<&Object>,
+    run: impl FnOnce(&dyn FileSystem) -> std::result::Result<T, String>,
+) -> std::result::Result<T, String> {
+    let napi_fs = file_system
+        .map(NapiFileSystem::new)
+        .transpose()
+        .map_err(|e| format!("Invalid file system adapter: {}", e.reason))?;
+    let fs: &dyn FileSystem = match &napi_fs {
+        Some(napi_fs) => napi_fs,
+        None => &OsFileSystem,
+    };
+    run(fs)
+}
+
 #[napi]
 #[allow(clippy::needless_pass_by_value)]
 pub fn parse_exports_napi(source: String) -> Result<Vec<ExportInfo>> {
@@ -40,9 +90,213 @@ pub fn sort_exports_napi(exports: Vec<ExportInfo>) -> Vec<ExportInfo> {
     sort_exports_internal(exports)
 }
 
+#[napi]
+#[allow(clippy::needless_pass_by_value)]
+pub fn sort_exports_by_strategy_napi(
+    exports: Vec<ExportInfo>,
+    strategy: Option<String>,
+    specifier_strategy: Option<String>,
+) -> Result<Vec<ExportInfo>> {
+    let statements = SortStrategy::from_option(strategy.as_deref())
+        .map_err(|e: String| napi::Error::new(napi::Status::InvalidArg, e))?;
+    let specifiers = SortStrategy::from_option(specifier_strategy.as_deref())
+        .map_err(|e: String| napi::Error::new(napi::Status::InvalidArg, e))?;
+    Ok(sort_exports_by_internal(exports, statements, specifiers))
+}
+
 #[napi]
 #[must_use]
 #[allow(clippy::needless_pass_by_value)]
 pub fn reconstruct_source_napi(source: String, exports: Vec<ExportInfo>) -> String {
     reconstruct_source_internal(&source, exports)
 }
+
+#[napi]
+#[must_use]
+#[allow(clippy::needless_pass_by_value)]
+pub fn detect_conflicts_napi(exports: Vec<ExportInfo>) -> Vec<ExportConflict> {
+    detect_conflicts_internal(&exports)
+}
+
+#[napi]
+#[allow(clippy::needless_pass_by_value)]
+pub fn resolve_barrel_napi(
+    file_path: String,
+    options: Option<BarrelLoaderOptions>,
+    file_system: Option<Object>,
+) -> Result<Vec<ExportInfo>> {
+    with_loader(options, file_system.as_ref(), |loader| {
+        loader
+            .resolver(&file_path)?
+            .resolve_barrel(Path::new(&file_path))
+    })
+    .map(|resolved| resolved.into_iter().map(|r| r.export).collect())
+    .map_err(|e: String| napi::Error::new(napi::Status::GenericFailure, e))
+}
+
+#[napi]
+#[allow(clippy::needless_pass_by_value)]
+pub fn resolve_source_napi(
+    importer: String,
+    specifier: String,
+    options: Option<BarrelLoaderOptions>,
+    file_system: Option<Object>,
+) -> Result<Option<String>> {
+    with_loader(options, file_system.as_ref(), |loader| {
+        loader.resolve_source(&importer, &specifier)
+    })
+    .map(|file| file.map(|path| path.to_string_lossy().into_owned()))
+    .map_err(|e: String| napi::Error::new(napi::Status::InvalidArg, e))
+}
+
+#[napi]
+#[allow(clippy::needless_pass_by_value)]
+pub fn source_specifier_napi(
+    importer: String,
+    file: String,
+    options: Option<BarrelLoaderOptions>,
+    file_system: Option<Object>,
+) -> Result<String> {
+    with_loader(options, file_system.as_ref(), |loader| {
+        loader.source_specifier(&importer, &file)
+    })
+    .map_err(|e: String| napi::Error::new(napi::Status::InvalidArg, e))
+}
+
+#[napi]
+#[allow(clippy::needless_pass_by_value)]
+pub fn resolve_sources_napi(
+    importer: String,
+    specifiers: Vec<String>,
+    options: Option<BarrelLoaderOptions>,
+    file_system: Option<Object>,
+) -> Result<Vec<Option<String>>> {
+    with_loader(options, file_system.as_ref(), |loader| {
+        loader.resolve_sources(&importer, &specifiers)
+    })
+    .map(|files| {
+        files
+            .into_iter()
+            .map(|file| file.map(|path| path.to_string_lossy().into_owned()))
+            .collect()
+    })
+    .map_err(|e: String| napi::Error::new(napi::Status::InvalidArg, e))
+}
+
+#[napi]
+#[allow(clippy::needless_pass_by_value)]
+pub fn rebase_sources_napi(
+    from: String,
+    importer: String,
+    sources: Vec<String>,
+    options: Option<BarrelLoaderOptions>,
+    file_system: Option<Object>,
+) -> Result<Vec<String>> {
+    with_loader(options, file_system.as_ref(), |loader| {
+        loader.rebase_sources(&from, &importer, &sources)
+    })
+    .map_err(|e: String| napi::Error::new(napi::Status::InvalidArg, e))
+}
+
+#[napi]
+#[allow(clippy::needless_pass_by_value)]
+pub fn rewrite_imports_napi(
+    source: String,
+    file_path: String,
+    options: Option<BarrelLoaderOptions>,
+    file_system: Option<Object>,
+) -> Result<String> {
+    with_loader(options, file_system.as_ref(), |loader| {
+        loader.rewrite_imports(&source, &file_path)
+    })
+    .map_err(|e: String| napi::Error::new(napi::Status::GenericFailure, e))
+}
+
+#[napi]
+#[must_use]
+pub fn module_cache_stats_napi(cache_directory: String) -> CacheStats {
+    ModuleCache::new(cache_directory).stats()
+}
+
+#[napi]
+#[allow(clippy::needless_pass_by_value)]
+pub fn detect_cycles_napi(
+    file_path: String,
+    options: Option<BarrelLoaderOptions>,
+    file_system: Option<Object>,
+) -> Result<Vec<ExportCycle>> {
+    with_loader(options, file_system.as_ref(), |loader| loader.find_cycles(&file_path))
+        .map_err(|e: String| napi::Error::new(napi::Status::GenericFailure, e))
+}
+
+#[napi]
+#[allow(clippy::needless_pass_by_value)]
+pub fn analyze_side_effects_napi(
+    file_path: String,
+    options: Option<BarrelLoaderOptions>,
+    file_system: Option<Object>,
+) -> Result<Vec<ModuleSideEffects>> {
+    with_loader(options, file_system.as_ref(), |loader| loader.analyze_side_effects(&file_path))
+        .map_err(|e: String| napi::Error::new(napi::Status::GenericFailure, e))
+}
+
+/// Reverse dependency graph kept alive between rebuilds, for watch-mode integrations
+/// Each call reads through the given filesystem adapter, or the disk
+#[napi(js_name = "ExportGraph")]
+pub struct ExportGraphNapi {
+    graph: ExportGraph,
+}
+
+#[napi]
+impl ExportGraphNapi {
+    #[napi(constructor)]
+    #[must_use]
+    pub fn new(options: Option<BarrelLoaderOptions>) -> Self {
+        Self { graph: ExportGraph::new(options.unwrap_or_default()) }
+    }
+
+    #[napi]
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn add_barrel(&mut self, barrel: String, file_system: Option<Object>) -> Result<()> {
+        with_file_system(file_system.as_ref(), |fs| self.graph.add_barrel(&fs, Path::new(&barrel)))
+            .map_err(|e: String| napi::Error::new(napi::Status::GenericFailure, e))
+    }
+
+    #[napi]
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn remove_barrel(&mut self, barrel: String) -> bool {
+        self.graph.remove_barrel(Path::new(&barrel))
+    }
+
+    #[napi]
+    #[must_use]
+    pub fn barrels(&self) -> Vec<String> {
+        to_strings(self.graph.barrels())
+    }
+
+    #[napi]
+    #[must_use]
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn dependents(&self, file: String) -> Vec<String> {
+        to_strings(self.graph.dependents(Path::new(&file)))
+    }
+
+    #[napi]
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn update(
+        &mut self,
+        changed: Vec<String>,
+        file_system: Option<Object>,
+    ) -> Result<Vec<AffectedBarrel>> {
+        let changed: Vec<PathBuf> = changed.into_iter().map(PathBuf::from).collect();
+        with_file_system(file_system.as_ref(), |fs| Ok(self.graph.update(&fs, &changed)))
+            .map_err(|e: String| napi::Error::new(napi::Status::GenericFailure, e))
+    }
+}
+
+fn to_strings(paths: Vec<PathBuf>) -> Vec<String> {
+    paths
+        .into_iter()
+        .map(|path| path.to_string_lossy().into_owned())
+        .collect()
+}
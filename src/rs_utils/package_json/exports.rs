@@ -0,0 +1,74 @@
+use serde_json::Value;
+
+/// Resolve a subpath through a package `exports` (or `imports`) field
+/// Follows Node's rules: exact keys first, then the `*` pattern with the longest prefix,
+/// with condition objects matched in their own key order
+#[must_use]
+pub fn resolve_package_target(map: &Value, subpath: &str, conditions: &[String]) -> Option<String> {
+    // Sugar: `"exports": "./index.js"` or a conditions object applies to "."
+    let is_subpath_map = map.as_object().is_some_and(|object| {
+        object
+            .keys()
+            .any(|key| key.starts_with('.') || key.starts_with('#'))
+    });
+    if !is_subpath_map {
+        return (subpath == ".")
+            .then(|| resolve_target(map, None, conditions))
+            .flatten();
+    }
+
+    let object = map.as_object()?;
+    if let Some(target) = object.get(subpath) {
+        if !subpath.contains('*') {
+            return resolve_target(target, None, conditions);
+        }
+    }
+
+    let mut best: Option<(&str, &Value, &str)> = None;
+    for (key, target) in object {
+        let Some((prefix, suffix)) = key.split_once('*') else {
+            continue;
+        };
+        let Some(captured) = subpath
+            .strip_prefix(prefix)
+            .and_then(|rest| rest.strip_suffix(suffix))
+        else {
+            continue;
+        };
+        if subpath.len() < key.len() {
+            continue;
+        }
+        if best.is_none_or(|(best_key, _, _)| pattern_key_cmp(key, best_key)) {
+            best = Some((key, target, captured));
+        }
+    }
+
+    let (_, target, captured) = best?;
+    resolve_target(target, Some(captured), conditions)
+}
+
+/// Resolve one target value: a string, an array of fallbacks or a conditions object
+fn resolve_target(target: &Value, captured: Option<&str>, conditions: &[String]) -> Option<String> {
+    match target {
+        Value::String(path) => {
+            Some(captured.map_or_else(|| path.clone(), |c| path.replace('*', c)))
+        }
+        Value::Array(targets) => targets
+            .iter()
+            .find_map(|t| resolve_target(t, captured, conditions)),
+        Value::Object(branches) => branches.iter().find_map(|(condition, t)| {
+            (condition == "default" || conditions.iter().any(|c| c == condition))
+                .then(|| resolve_target(t, captured, conditions))
+                .flatten()
+        }),
+        _ => None,
+    }
+}
+
+/// True when `key` is a more specific pattern than `best`
+/// Longer prefixes win, then longer keys, as in Node's `PATTERN_KEY_COMPARE`
+fn pattern_key_cmp(key: &str, best: &str) -> bool {
+    let prefix_len = |k: &str| k.find('*').map_or(k.len(), |i| i + 1);
+    let (key_prefix, best_prefix) = (prefix_len(key), prefix_len(best));
+    key_prefix > best_prefix || (key_prefix == best_prefix && key.len() > best.len())
+}
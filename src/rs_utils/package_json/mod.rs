@@ -0,0 +1,113 @@
+mod exports;
+mod side_effects;
+
+pub use exports::resolve_package_target;
+pub use side_effects::SideEffects;
+
+use super::file_system::{read_file, FileSystem};
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+
+/// The parts of a `package.json` used for module resolution
+#[derive(Debug, Clone, Default)]
+pub struct PackageJson {
+    /// Directory containing the `package.json`
+    pub dir: PathBuf,
+    pub exports: Option<Value>,
+    /// Subpath imports (`#internal/*`) private to the package
+    pub imports: Option<Value>,
+    /// `sideEffects`, when it marks some or all modules as pure
+    pub side_effects: Option<SideEffects>,
+    /// Top-level string fields such as `main`, `module` and `types`
+    pub fields: serde_json::Map<String, Value>,
+}
+
+impl PackageJson {
+    /// Read `package.json` from a package directory
+    pub fn load<F: FileSystem>(fs: &F, dir: &Path) -> Result<Self, String> {
+        let path = dir.join("package.json");
+        let source = read_file(fs, &path)?;
+        let json: Value = serde_json::from_str(&source)
+            .map_err(|e| format!("Failed to parse {}: {e}", path.display()))?;
+        let Value::Object(mut fields) = json else {
+            return Err(format!("{} is not an object", path.display()));
+        };
+
+        Ok(Self {
+            dir: dir.to_path_buf(),
+            exports: fields.remove("exports"),
+            imports: fields.remove("imports"),
+            side_effects: SideEffects::from_field(fields.get("sideEffects")),
+            fields,
+        })
+    }
+
+    /// Resolve a subpath (`.` or `./feature`) to a path inside the package
+    /// `exports` is authoritative when present; otherwise main fields are used for `.`
+    /// and other subpaths map directly onto the package directory
+    #[must_use]
+    pub fn resolve_subpath(
+        &self,
+        subpath: &str,
+        conditions: &[String],
+        main_fields: &[String],
+    ) -> Option<PathBuf> {
+        if let Some(exports) = &self.exports {
+            let target = resolve_package_target(exports, subpath, conditions)?;
+            return Some(self.dir.join(target));
+        }
+
+        if subpath == "." {
+            return self.main_entry(main_fields);
+        }
+        Some(self.dir.join(subpath))
+    }
+
+    /// The entry named by the first main field present, ignoring `exports`
+    #[must_use]
+    pub fn main_entry(&self, main_fields: &[String]) -> Option<PathBuf> {
+        main_fields.iter().find_map(|field| {
+            let entry = self.fields.get(field)?.as_str()?;
+            Some(self.dir.join(entry))
+        })
+    }
+}
+
+/// Find the directory of the `package.json` that owns `dir`
+#[must_use]
+pub fn find_package_scope<F: FileSystem>(fs: &F, dir: &Path) -> Option<PathBuf> {
+    dir.ancestors()
+        .find(|ancestor| fs.is_file(&ancestor.join("package.json")))
+        .map(Path::to_path_buf)
+}
+
+/// Split a bare specifier into its package name and subpath
+/// `@acme/ui/button` becomes (`@acme/ui`, `./button`) and `lodash` becomes (`lodash`, `.`)
+#[must_use]
+pub fn split_package_specifier(specifier: &str) -> Option<(&str, String)> {
+    let segments = if specifier.starts_with('@') { 2 } else { 1 };
+    let mut end = 0;
+    for (count, (index, _)) in specifier.match_indices('/').enumerate() {
+        if count + 1 == segments {
+            end = index;
+            break;
+        }
+    }
+    let (name, rest) = if end == 0 {
+        (specifier, "")
+    } else {
+        specifier.split_at(end)
+    };
+    if name.is_empty() || (segments == 2 && !name.contains('/')) {
+        return None;
+    }
+    Some((name, format!(".{rest}")))
+}
+
+/// Find a package directory by walking up `node_modules` folders from `dir`
+#[must_use]
+pub fn find_package_dir<F: FileSystem>(fs: &F, dir: &Path, name: &str) -> Option<PathBuf> {
+    dir.ancestors()
+        .map(|ancestor| ancestor.join("node_modules").join(name))
+        .find(|candidate| fs.is_file(&candidate.join("package.json")))
+}
@@ -0,0 +1,77 @@
+use regex::Regex;
+use serde_json::Value;
+use std::path::Path;
+
+/// A package's `sideEffects` field
+#[derive(Debug, Clone)]
+pub enum SideEffects {
+    /// `false`: every module of the package is pure
+    None,
+    /// `true`: every module of the package has side effects
+    All,
+    /// Only modules matching one of the globs have side effects
+    Globs(Vec<Regex>),
+}
+
+impl SideEffects {
+    /// Read the field; a missing field or an unusable value give `None`, leaving
+    /// the verdict to analysis of the module itself
+    #[must_use]
+    pub fn from_field(value: Option<&Value>) -> Option<Self> {
+        match value? {
+            Value::Bool(false) => Some(Self::None),
+            Value::Bool(true) => Some(Self::All),
+            Value::Array(patterns) => Some(Self::Globs(
+                patterns
+                    .iter()
+                    .filter_map(Value::as_str)
+                    .filter_map(glob_to_regex)
+                    .collect(),
+            )),
+            _ => None,
+        }
+    }
+
+    /// Whether the module at `relative` (to the package directory) has side effects
+    #[must_use]
+    pub fn matches(&self, relative: &Path) -> bool {
+        let path = relative.to_string_lossy().replace('\\', "/");
+        match self {
+            Self::None => false,
+            Self::All => true,
+            Self::Globs(globs) => globs.iter().any(|glob| glob.is_match(&path)),
+        }
+    }
+}
+
+/// Compile a `sideEffects` glob as webpack does: patterns without a slash match
+/// the file name in any directory, `**` spans directories and `*` does not
+fn glob_to_regex(pattern: &str) -> Option<Regex> {
+    let pattern = pattern.strip_prefix("./").unwrap_or(pattern);
+    let pattern = if pattern.contains('/') {
+        pattern.to_string()
+    } else {
+        format!("**/{pattern}")
+    };
+
+    let mut regex = String::from("^");
+    let mut chars = pattern.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '*' if chars.peek() == Some(&'*') => {
+                chars.next();
+                if chars.peek() == Some(&'/') {
+                    chars.next();
+                    regex.push_str("(?:.*/)?");
+                } else {
+                    regex.push_str(".*");
+                }
+            }
+            '*' => regex.push_str("[^/]*"),
+            '?' => regex.push_str("[^/]"),
+            c => regex.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+    regex.push('$');
+    Regex::new(&regex).ok()
+}
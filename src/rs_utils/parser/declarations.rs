@@ -0,0 +1,156 @@
+use super::scanner::{scan, split_top_level, Scan};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::sync::LazyLock;
+
+/// A name exported by a module's own declarations
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LocalExport {
+    pub name: String,
+    pub is_type: bool,
+    pub line: u32,
+}
+
+/// Patterns for declarations and their type flag
+const DECLARATION_PATTERNS: &[(&str, bool)] = &[
+    (r"^export\s+(?:declare\s+)?(?:async\s+)?function\s*\*?\s*(\w+)", false),
+    (r"^export\s+(?:declare\s+)?(?:abstract\s+)?class\s+(\w+)", false),
+    (r"^export\s+(?:declare\s+)?(?:const\s+)?enum\s+(\w+)", false),
+    (r"^export\s+(?:declare\s+)?(?:namespace|module)\s+(\w+)", false),
+    (r"^export\s+(?:declare\s+)?interface\s+(\w+)", true),
+    (r"^export\s+(?:declare\s+)?type\s+(\w+)\s*(?:<|=)", true),
+];
+
+/// Compiled once and shared by every parse, including parallel ones
+static DECLARATIONS: LazyLock<Option<Vec<(Regex, bool)>>> = LazyLock::new(compile_patterns);
+static VARIABLE: LazyLock<Option<Regex>> = LazyLock::new(|| {
+    Regex::new(r"(?s)^export\s+(?:declare\s+)?(?:const|let|var|using)\s+(.+)").ok()
+});
+static LOCAL_LIST: LazyLock<Option<Regex>> =
+    LazyLock::new(|| Regex::new(r"^export\s+(type\s+)?\{([^}]*)\}\s*;?\s*$").ok());
+
+/// Parse names a module exports through its own declarations
+/// Matches: `export const a`, `export function f`, `export interface I`,
+/// `export default ...` and local lists such as `export { a, b as c }`
+#[must_use]
+pub fn parse_local_exports(source: &str) -> Vec<LocalExport> {
+    let (Some(patterns), Some(variable), Some(list)) =
+        (DECLARATIONS.as_ref(), VARIABLE.as_ref(), LOCAL_LIST.as_ref())
+    else {
+        return Vec::new();
+    };
+
+    let scan = scan(source);
+    let mut exports: Vec<LocalExport> = Vec::new();
+    for (index, line) in source.lines().enumerate() {
+        let line_number = u32::try_from(index + 1).unwrap_or(u32::MAX);
+        let trimmed = line.trim();
+        if !trimmed.starts_with("export") {
+            continue;
+        }
+
+        let mut push = |name: &str, is_type: bool| {
+            exports.push(LocalExport { name: name.to_string(), is_type, line: line_number });
+        };
+
+        if is_default_export(trimmed) {
+            push("default", trimmed.contains("default interface"));
+        } else if let Some((name, is_type)) = patterns
+            .iter()
+            .find_map(|(re, is_type)| Some((re.captures(trimmed)?.get(1)?.as_str(), *is_type)))
+        {
+            push(name, is_type);
+        } else if variable.is_match(trimmed) {
+            let statement = statement_at(&scan, line_number).unwrap_or(trimmed);
+            let declarators = variable.captures(statement).and_then(|caps| caps.get(1));
+            for name in declarators.map_or_else(Vec::new, |m| declared_names(m.as_str())) {
+                push(name, false);
+            }
+        } else if let Some(caps) = list.captures(trimmed) {
+            let is_type_list = caps.get(1).is_some();
+            for item in caps.get(2).map_or("", |m| m.as_str()).split(',') {
+                let item = item.trim();
+                if item.is_empty() {
+                    continue;
+                }
+                let (item, inline_type) = item
+                    .strip_prefix("type ")
+                    .map_or((item, false), |rest| (rest.trim(), true));
+                let name = item
+                    .split_once(" as ")
+                    .map_or(item, |(_, alias)| alias.trim());
+                push(name, is_type_list || inline_type);
+            }
+        }
+    }
+
+    exports
+}
+
+fn compile_patterns() -> Option<Vec<(Regex, bool)>> {
+    DECLARATION_PATTERNS
+        .iter()
+        .map(|(pattern, is_type)| Regex::new(pattern).ok().map(|re| (re, *is_type)))
+        .collect()
+}
+
+/// Blanked text of the statement starting on `line`, which may run over later lines
+fn statement_at(scan: &Scan, line: u32) -> Option<&str> {
+    let statement = scan
+        .statements
+        .iter()
+        .find(|statement| statement.line == line)?;
+    Some(scan.code(statement))
+}
+
+fn is_default_export(line: &str) -> bool {
+    line.strip_prefix("export").is_some_and(|rest| {
+        rest.trim_start().starts_with("default") && rest.starts_with(char::is_whitespace)
+    })
+}
+
+/// Names bound by each declarator of `a = 1, { b, c } = d`
+/// Returns none if the declarators cannot be split, leaving the module unanalyzable
+fn declared_names(declarators: &str) -> Vec<&str> {
+    let declarators = declarators.trim().trim_end_matches(';');
+    split_top_level(declarators, ',')
+        .unwrap_or_default()
+        .into_iter()
+        .flat_map(binding_names)
+        .collect()
+}
+
+/// Names bound by a variable declarator, including simple destructuring
+fn binding_names(declaration: &str) -> Vec<&str> {
+    let declaration = declaration.trim();
+    if let Some(pattern) = declaration
+        .strip_prefix('{')
+        .or_else(|| declaration.strip_prefix('['))
+    {
+        let end = pattern.find(['}', ']']).unwrap_or(pattern.len());
+        return pattern[..end]
+            .split(',')
+            .filter_map(|part| {
+                let part = part.split('=').next()?.trim();
+                let name = part.rsplit(':').next()?.trim().trim_start_matches("...");
+                is_identifier(name).then_some(name)
+            })
+            .collect();
+    }
+
+    let end = declaration
+        .find(|c: char| !(c.is_alphanumeric() || c == '_' || c == '$'))
+        .unwrap_or(declaration.len());
+    let name = &declaration[..end];
+    if is_identifier(name) {
+        vec![name]
+    } else {
+        Vec::new()
+    }
+}
+
+fn is_identifier(name: &str) -> bool {
+    name.chars()
+        .next()
+        .is_some_and(|c| c.is_alphabetic() || c == '_' || c == '$')
+}
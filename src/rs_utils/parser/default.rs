@@ -1,13 +1,18 @@
 use regex::Regex;
+use std::sync::LazyLock;
+
+static DEFAULT_EXPORT: LazyLock<Option<Regex>> = LazyLock::new(|| {
+    Regex::new(
+        r#"export\s+(?:type\s+)?\{\s*default\s*(?:as\s+(\w+))?\s*\}\s+from\s+['"]([^'"]+)['"]"#,
+    )
+    .ok()
+});
 
 /// Parse default exports from a line
 /// Matches: export { default } from "./module" or export { default as Name } from "./module"
 #[must_use]
 pub fn parse_default_export(line: &str) -> Option<(String, String)> {
-    let re = Regex::new(
-        r#"export\s+(?:type\s+)?\{\s*default\s*(?:as\s+(\w+))?\s*\}\s+from\s+['"]([^'"]+)['"]"#,
-    )
-    .ok()?;
+    let re = DEFAULT_EXPORT.as_ref()?;
     let caps = re.captures(line)?;
     let specifier = caps
         .get(1)
@@ -0,0 +1,134 @@
+use super::scanner::scan;
+use regex::Regex;
+use std::collections::HashSet;
+use std::ops::Range;
+use std::sync::LazyLock;
+
+/// Static import with a binding clause; the clause may span several lines
+static IMPORT: LazyLock<Option<Regex>> = LazyLock::new(|| {
+    Regex::new(
+        r#"(?m)^([ \t]*)import\s+(type\s+)?([^'";]*?)\s*\bfrom\s*(['"])([^'"]+)['"][ \t]*(;?)"#,
+    )
+    .ok()
+});
+
+/// An `import ... from` statement of a consumer module
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportStatement {
+    /// Byte range of the whole statement in the source
+    pub range: Range<usize>,
+    pub indent: String,
+    /// `import type { ... }`
+    pub is_type: bool,
+    /// Default import as `default`, then the names inside braces
+    pub names: Vec<ImportedName>,
+    /// `import * as ns`, which cannot be split by name
+    pub namespace: Option<String>,
+    pub source: String,
+    pub quote: char,
+    pub semicolon: bool,
+}
+
+/// One binding of an import clause: `imported as local`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportedName {
+    pub imported: String,
+    pub local: String,
+    /// Inline `type` modifier, as in `import { type Props }`
+    pub is_type: bool,
+}
+
+/// Parse the static imports of a module
+/// Side-effect imports (`import "./styles.css"`) bind nothing and are skipped, and
+/// so is text that only looks like an import inside strings, templates or comments
+#[must_use]
+pub fn parse_imports(source: &str) -> Vec<ImportStatement> {
+    let Some(re) = IMPORT.as_ref() else {
+        return Vec::new();
+    };
+    let starts: HashSet<usize> = scan(source)
+        .statements
+        .iter()
+        .map(|statement| statement.range.start)
+        .collect();
+    re.captures_iter(source)
+        .filter_map(|caps| {
+            let whole = caps.get(0)?;
+            let indent = caps.get(1)?;
+            if !starts.contains(&indent.end()) {
+                return None;
+            }
+            let (names, namespace) = parse_clause(caps.get(3)?.as_str())?;
+            Some(ImportStatement {
+                range: whole.range(),
+                indent: indent.as_str().to_string(),
+                is_type: caps.get(2).is_some(),
+                names,
+                namespace,
+                source: caps.get(5)?.as_str().to_string(),
+                quote: caps.get(4)?.as_str().chars().next()?,
+                semicolon: caps.get(6).is_some_and(|m| !m.is_empty()),
+            })
+        })
+        .collect()
+}
+
+/// Split `Default, { a, b as c }` or `Default, * as ns` into bindings
+fn parse_clause(clause: &str) -> Option<(Vec<ImportedName>, Option<String>)> {
+    let clause = clause.trim();
+    let (default, rest) = if clause.starts_with('{') || clause.starts_with('*') {
+        (None, clause)
+    } else {
+        match clause.split_once(',') {
+            Some((default, rest)) => (Some(default.trim()), rest.trim()),
+            None => (Some(clause), ""),
+        }
+    };
+
+    let mut names: Vec<ImportedName> = Vec::new();
+    if let Some(local) = default {
+        if !is_identifier(local) {
+            return None;
+        }
+        names.push(ImportedName {
+            imported: "default".to_string(),
+            local: local.to_string(),
+            is_type: false,
+        });
+    }
+
+    if let Some(namespace) = rest.strip_prefix('*') {
+        let local = namespace.trim().strip_prefix("as")?.trim();
+        return is_identifier(local).then(|| (names, Some(local.to_string())));
+    }
+    if rest.is_empty() {
+        return Some((names, None));
+    }
+
+    let inner = rest.strip_prefix('{')?.strip_suffix('}')?;
+    for item in inner
+        .split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+    {
+        let (item, is_type) = item
+            .strip_prefix("type ")
+            .map_or((item, false), |rest| (rest.trim(), true));
+        let (imported, local) = item
+            .split_once(" as ")
+            .map_or((item, item), |(imported, local)| (imported.trim(), local.trim()));
+        names.push(ImportedName {
+            imported: imported.to_string(),
+            local: local.to_string(),
+            is_type,
+        });
+    }
+    Some((names, None))
+}
+
+fn is_identifier(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '_' || c == '$')
+}
@@ -1,16 +1,24 @@
+mod declarations;
 mod default;
 mod export_builder;
+mod imports;
 mod line_parser;
 mod named;
 mod namespace;
+mod scanner;
+mod side_effects;
 
+pub use declarations::{parse_local_exports, LocalExport};
 pub use default::parse_default_export;
 pub use export_builder::create_export;
+pub use imports::{parse_imports, ImportStatement, ImportedName};
 pub use named::parse_named_export;
 pub use namespace::parse_namespace_export;
+pub use side_effects::find_side_effects;
 
 use crate::types::ExportInfo;
 use line_parser::parse_line;
+use std::ops::Range;
 
 /// Parse exports from source code
 #[allow(clippy::unnecessary_wraps)]
@@ -23,3 +31,32 @@ pub fn parse_exports(source: &str) -> Result<Vec<ExportInfo>, String> {
 
     Ok(exports)
 }
+
+/// Byte ranges of the top-level re-exports of a module, `export ... from "..."`,
+/// each including its trailing `;`
+#[must_use]
+pub fn find_reexports(source: &str) -> Vec<Range<usize>> {
+    let scan = scanner::scan(source);
+    scan.statements
+        .iter()
+        .filter(|statement| is_reexport(scan.code(statement)))
+        .map(|statement| statement.range.clone())
+        .collect()
+}
+
+/// Whether a blanked statement is `export { ... } from` or `export * from`
+fn is_reexport(statement: &str) -> bool {
+    let Some(rest) = statement.strip_prefix("export") else {
+        return false;
+    };
+    let rest = rest.trim_start();
+    let rest = rest.strip_prefix("type").map_or(rest, str::trim_start);
+    let clause = rest.starts_with(['{', '*']);
+    let source = statement
+        .trim_end_matches(';')
+        .trim_end()
+        .strip_suffix(['"', '\''])
+        .and_then(|before| before.trim_end_matches(' ').strip_suffix(['"', '\'']))
+        .map(str::trim_end);
+    clause && source.is_some_and(|before| before.ends_with("from"))
+}
@@ -1,10 +1,15 @@
 use regex::Regex;
+use std::sync::LazyLock;
+
+static NAMED_EXPORT: LazyLock<Option<Regex>> = LazyLock::new(|| {
+    Regex::new(r#"export\s+(?:type\s+)?\{([^}]+)\}\s+from\s+['"]([^'"]+)['"]"#).ok()
+});
 
 /// Parse named exports from a line
 /// Matches: export { foo, bar } from "./module"
 #[must_use]
 pub fn parse_named_export(line: &str) -> Option<Vec<(String, String)>> {
-    let re = Regex::new(r#"export\s+(?:type\s+)?\{([^}]+)\}\s+from\s+['"]([^'"]+)['"]"#).ok()?;
+    let re = NAMED_EXPORT.as_ref()?;
     let caps = re.captures(line)?;
     let specifiers = caps.get(1)?.as_str();
     let source = caps.get(2)?.as_str();
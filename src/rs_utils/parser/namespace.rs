@@ -1,12 +1,15 @@
 use regex::Regex;
+use std::sync::LazyLock;
+
+static NAMESPACE_EXPORT: LazyLock<Option<Regex>> = LazyLock::new(|| {
+    Regex::new(r#"export\s+(?:type\s+)?\*\s+(?:as\s+(\w+)\s+)?from\s+['"]([^'"]+)['"]"#).ok()
+});
 
 /// Parse namespace exports from a line
 /// Matches: export * from "./module" or export * as helpers from "./module"
 #[must_use]
 pub fn parse_namespace_export(line: &str) -> Option<(String, String)> {
-    let re =
-        Regex::new(r#"export\s+(?:type\s+)?\*\s+(?:as\s+(\w+)\s+)?from\s+['"]([^'"]+)['"]"#)
-            .ok()?;
+    let re = NAMESPACE_EXPORT.as_ref()?;
     let caps = re.captures(line)?;
     let specifier = caps
         .get(1)
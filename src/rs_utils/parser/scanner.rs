@@ -0,0 +1,208 @@
+use std::iter::Peekable;
+use std::ops::Range;
+use std::str::Chars;
+
+/// Characters that, ending a line, carry the statement on to the next one
+const CONTINUES_AFTER: &str = "=,([{+-*/%&|^!?:<>.";
+/// Characters that, starting a line, continue the statement of the previous one
+const CONTINUES_BEFORE: &str = ".?:,=+-*/%&|^<>";
+
+/// A module's source with comments and the contents of strings and template
+/// literals blanked out, and its top-level statements
+/// Blanked characters become spaces of the same byte length and newlines are
+/// kept, so ranges and line numbers apply to both the source and `code`
+#[derive(Debug, Clone)]
+pub struct Scan {
+    pub code: String,
+    pub statements: Vec<Statement>,
+}
+
+/// One top-level statement, without surrounding whitespace and comments
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Statement {
+    /// Byte range in the source, including a trailing `;`
+    pub range: Range<usize>,
+    /// 1-based line the statement starts on
+    pub line: u32,
+}
+
+impl Scan {
+    /// Blanked text of a statement
+    #[must_use]
+    pub fn code(&self, statement: &Statement) -> &str {
+        &self.code[statement.range.clone()]
+    }
+}
+
+/// Scan a module into its top-level statements
+/// Statements end at `;`, or at a line break outside brackets unless an operator
+/// joins the lines; regex literals are not recognized
+#[must_use]
+pub fn scan(source: &str) -> Scan {
+    let code = blank_literals(source);
+    let statements = split_statements(&code);
+    Scan { code, statements }
+}
+
+/// Replace comments and literal contents with spaces; quotes and backticks stay
+fn blank_literals(source: &str) -> String {
+    let mut code = String::with_capacity(source.len());
+    let mut chars = source.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '/' if chars.peek() == Some(&'/') => {
+                blank(&mut code, c);
+                while let Some(&next) = chars.peek() {
+                    if next == '\n' {
+                        break;
+                    }
+                    blank(&mut code, next);
+                    chars.next();
+                }
+            }
+            '/' if chars.peek() == Some(&'*') => {
+                blank(&mut code, c);
+                let mut previous = ' ';
+                for next in chars.by_ref() {
+                    blank(&mut code, next);
+                    if previous == '*' && next == '/' {
+                        break;
+                    }
+                    previous = next;
+                }
+            }
+            '\'' | '"' | '`' => blank_literal(&mut code, &mut chars, c),
+            c => code.push(c),
+        }
+    }
+    code
+}
+
+/// Blank a string or template literal after its opening `quote`
+fn blank_literal(code: &mut String, chars: &mut Peekable<Chars<'_>>, quote: char) {
+    code.push(quote);
+    while let Some(next) = chars.next() {
+        if next == quote {
+            code.push(quote);
+            return;
+        }
+        // Plain strings end at the line; templates may span lines
+        if next == '\n' && quote != '`' {
+            code.push('\n');
+            return;
+        }
+        blank(code, next);
+        if next == '\\' {
+            if let Some(escaped) = chars.next() {
+                blank(code, escaped);
+            }
+        }
+    }
+}
+
+fn blank(code: &mut String, c: char) {
+    if c == '\n' {
+        code.push('\n');
+    } else {
+        code.extend(std::iter::repeat_n(' ', c.len_utf8()));
+    }
+}
+
+fn split_statements(code: &str) -> Vec<Statement> {
+    let mut statements: Vec<Statement> = Vec::new();
+    let mut depth: usize = 0;
+    let mut start: Option<(usize, u32)> = None;
+    let mut last: Option<(usize, char)> = None;
+    let mut line: u32 = 1;
+    let mut in_template = false;
+    for (index, c) in code.char_indices() {
+        if c == '\n' {
+            line += 1;
+        }
+        // Line breaks inside a template literal belong to it
+        if c.is_whitespace() || (in_template && c != '`') {
+            continue;
+        }
+        match start {
+            None => start = Some((index, line)),
+            // A line break at the top level ends the statement unless an
+            // operator on either side joins the lines
+            Some((begin, begin_line)) if depth == 0 && !in_template => {
+                if let Some((end, previous)) = last {
+                    let broke = code[end..index].contains('\n');
+                    if broke && !CONTINUES_AFTER.contains(previous) && !CONTINUES_BEFORE.contains(c)
+                    {
+                        statements.push(Statement {
+                            range: begin..end + previous.len_utf8(),
+                            line: begin_line,
+                        });
+                        start = Some((index, line));
+                    }
+                }
+            }
+            Some(_) => {}
+        }
+        match c {
+            '`' => in_template = !in_template,
+            '{' | '(' | '[' => depth += 1,
+            '}' | ')' | ']' => depth = depth.saturating_sub(1),
+            ';' if depth == 0 => {
+                if let Some((begin, begin_line)) = start.take() {
+                    statements.push(Statement { range: begin..index + 1, line: begin_line });
+                }
+                last = None;
+                continue;
+            }
+            _ => {}
+        }
+        last = Some((index, c));
+    }
+    if let (Some((begin, begin_line)), Some((end, previous))) = (start, last) {
+        statements.push(Statement { range: begin..end + previous.len_utf8(), line: begin_line });
+    }
+    statements
+}
+
+/// Split blanked code at `separator` outside brackets
+/// Returns `None` when the brackets do not balance
+#[must_use]
+pub fn split_top_level(code: &str, separator: char) -> Option<Vec<&str>> {
+    let mut parts: Vec<&str> = Vec::new();
+    let mut depth: usize = 0;
+    let mut begin = 0;
+    for (index, c) in code.char_indices() {
+        match c {
+            '{' | '(' | '[' => depth += 1,
+            '}' | ')' | ']' => depth = depth.checked_sub(1)?,
+            c if c == separator && depth == 0 => {
+                parts.push(&code[begin..index]);
+                begin = index + c.len_utf8();
+            }
+            _ => {}
+        }
+    }
+    if depth != 0 {
+        return None;
+    }
+    parts.push(&code[begin..]);
+    Some(parts)
+}
+
+/// Byte index of the bracket closing the one at `open`, in blanked code
+#[must_use]
+pub fn matching_bracket(code: &str, open: usize) -> Option<usize> {
+    let mut depth: usize = 0;
+    for (index, c) in code[open..].char_indices() {
+        match c {
+            '{' | '(' | '[' => depth += 1,
+            '}' | ')' | ']' => {
+                depth = depth.checked_sub(1)?;
+                if depth == 0 {
+                    return Some(open + index);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
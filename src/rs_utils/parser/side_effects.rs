@@ -0,0 +1,240 @@
+use super::scanner::{matching_bracket, scan, split_top_level};
+
+/// Words before `(` that do not make it a call
+const NOT_CALLEES: &[&str] = &[
+    "as",
+    "async",
+    "case",
+    "in",
+    "instanceof",
+    "keyof",
+    "of",
+    "return",
+    "satisfies",
+    "typeof",
+    "void",
+];
+
+/// Find top-level statements that run code when the module is imported: calls,
+/// side-effect imports such as `import "./styles.css"`, and assignments to globals
+/// Returns their 1-based line numbers; declarations, re-exports and directives are pure
+#[must_use]
+pub fn find_side_effects(source: &str) -> Vec<u32> {
+    let scan = scan(source);
+    scan.statements
+        .iter()
+        .filter(|statement| is_side_effect(scan.code(statement)))
+        .map(|statement| statement.line)
+        .collect()
+}
+
+/// Whether a top-level statement evaluates code, given its blanked text
+fn is_side_effect(statement: &str) -> bool {
+    let statement = statement.trim_end_matches(';').trim();
+    if statement.is_empty() || statement.starts_with(['\'', '"']) {
+        return false;
+    }
+    let (word, rest) = keyword(statement);
+    match word {
+        // Bare imports run the module for its effects; `import(...)` is a call
+        "import" => rest.trim_start().starts_with(['\'', '"', '(']),
+        "export" => is_export_side_effect(rest.trim_start()),
+        _ => declaration_side_effect(statement).unwrap_or(true),
+    }
+}
+
+fn is_export_side_effect(rest: &str) -> bool {
+    if rest.starts_with(['*', '{']) {
+        return false;
+    }
+    match keyword(rest) {
+        ("default", value) => is_default_side_effect(value.trim_start()),
+        ("as" | "import", _) => false,
+        // TypeScript's `export = value`
+        _ if rest.starts_with('=') => has_effect(&rest[1..]),
+        _ => declaration_side_effect(rest).unwrap_or(true),
+    }
+}
+
+/// Declarations and classes are pure; any other default export is an expression
+fn is_default_side_effect(value: &str) -> bool {
+    match keyword(value).0 {
+        "function" | "class" | "interface" | "abstract" => false,
+        "async" if keyword(keyword(value).1.trim_start()).0 == "function" => false,
+        _ => has_effect(value),
+    }
+}
+
+/// Whether a declaration evaluates code, or `None` if `statement` is not one
+/// Only variable initializers are checked; function and class bodies do not run
+fn declaration_side_effect(statement: &str) -> Option<bool> {
+    let (word, rest) = keyword(statement);
+    if !rest.starts_with(char::is_whitespace) && !rest.starts_with(['{', '*']) {
+        return None;
+    }
+    match word {
+        "const" | "let" | "var" | "using" => Some(has_initializer_effect(rest)),
+        "abstract" | "async" | "class" | "declare" | "enum" | "function" | "interface"
+        | "module" | "namespace" | "type" => Some(false),
+        _ => None,
+    }
+}
+
+/// Whether any declarator's initializer evaluates code
+/// Declarations that cannot be split are treated as impure
+fn has_initializer_effect(declarators: &str) -> bool {
+    let Some(declarators) = split_top_level(declarators, ',') else {
+        return true;
+    };
+    declarators
+        .iter()
+        .any(|declarator| initializer(declarator).is_some_and(has_effect))
+}
+
+/// The expression after a declarator's top-level `=`
+fn initializer(declarator: &str) -> Option<&str> {
+    let bytes = declarator.as_bytes();
+    let mut depth: usize = 0;
+    for (index, &c) in bytes.iter().enumerate() {
+        match c {
+            b'{' | b'(' | b'[' => depth += 1,
+            b'}' | b')' | b']' => depth = depth.saturating_sub(1),
+            b'=' if depth == 0 => {
+                let previous = index.checked_sub(1).map(|i| bytes[i]);
+                let next = bytes.get(index + 1);
+                let comparison = matches!(previous, Some(b'=' | b'!' | b'<' | b'>'));
+                if !comparison && !matches!(next, Some(b'=' | b'>')) {
+                    return Some(&declarator[index + 1..]);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Whether evaluating a blanked expression runs code: calls, `new`, `await`,
+/// `delete`, assignments, increments and tagged templates
+/// Function, arrow, method and class bodies are skipped since they only run when called
+fn has_effect(expression: &str) -> bool {
+    let bytes = expression.as_bytes();
+    let mut index = 0;
+    while index < bytes.len() {
+        let c = bytes[index];
+        // Each step returns where scanning resumes, or `None` once code would run
+        let next = match c {
+            c if is_identifier_byte(c) => after_word(expression, index),
+            b'(' => after_parenthesis(expression, index),
+            b'`' if is_callee(&expression[..index]) => None,
+            b'`' => Some(
+                expression[index + 1..]
+                    .find('`')
+                    .map_or(bytes.len(), |end| index + end + 2),
+            ),
+            b'=' => match (index.checked_sub(1).map(|i| bytes[i]), bytes.get(index + 1)) {
+                (_, Some(b'>')) => Some(skip_arrow_body(expression, index + 2)),
+                (_, Some(b'=')) => Some(index + 2),
+                (Some(b'=' | b'!' | b'<' | b'>'), _) => Some(index + 1),
+                _ => None,
+            },
+            b'+' | b'-' if bytes.get(index + 1) == Some(&c) => None,
+            _ => Some(index + 1),
+        };
+        match next {
+            Some(next) => index = next,
+            None => return true,
+        }
+    }
+    false
+}
+
+/// Step over the identifier at `start`, skipping the bodies of functions and classes
+fn after_word(expression: &str, start: usize) -> Option<usize> {
+    let end = expression[start..]
+        .bytes()
+        .position(|c| !is_identifier_byte(c))
+        .map_or(expression.len(), |length| start + length);
+    if expression[..start].trim_end().ends_with('.') {
+        return Some(end);
+    }
+    match &expression[start..end] {
+        "new" | "await" | "delete" | "yield" => None,
+        "function" | "class" => skip_body(expression, end),
+        _ => Some(end),
+    }
+}
+
+/// Step over `(`: arrow functions and method shorthand `name() { ... }` are
+/// skipped whole, a call stops the scan and a group is entered
+fn after_parenthesis(expression: &str, open: usize) -> Option<usize> {
+    let close = matching_bracket(expression, open)?;
+    let after = expression[close + 1..].trim_start();
+    let after_index = expression.len() - after.len();
+    if after.starts_with("=>") {
+        return Some(skip_arrow_body(expression, after_index + 2));
+    }
+    if !is_callee(&expression[..open]) {
+        return Some(open + 1);
+    }
+    if !after.starts_with('{') {
+        return None;
+    }
+    matching_bracket(expression, after_index).map(|end| end + 1)
+}
+
+/// Whether code ending right before `(` or a backtick names something to call
+fn is_callee(before: &str) -> bool {
+    let before = before.trim_end();
+    let Some(last) = before.bytes().last() else {
+        return false;
+    };
+    if matches!(last, b')' | b']' | b'.') {
+        return true;
+    }
+    if !is_identifier_byte(last) {
+        return false;
+    }
+    let word = before
+        .rsplit(|c: char| !(c.is_alphanumeric() || c == '_' || c == '$'))
+        .next()
+        .unwrap_or(before);
+    !NOT_CALLEES.contains(&word)
+}
+
+/// End of the `{ ... }` body of a function or class starting at `from`
+fn skip_body(expression: &str, from: usize) -> Option<usize> {
+    let open = from + expression[from..].find('{')?;
+    matching_bracket(expression, open).map(|close| close + 1)
+}
+
+/// End of an arrow function body starting at `from`: a block, or an expression
+/// running to the next top-level `,` or closing bracket
+fn skip_arrow_body(expression: &str, from: usize) -> usize {
+    let body = expression[from..].trim_start();
+    let start = expression.len() - body.len();
+    if body.starts_with('{') {
+        return matching_bracket(expression, start).map_or(expression.len(), |close| close + 1);
+    }
+    let mut depth: usize = 0;
+    for (offset, c) in body.char_indices() {
+        match c {
+            '{' | '(' | '[' => depth += 1,
+            '}' | ')' | ']' | ',' | ';' if depth == 0 => return start + offset,
+            '}' | ')' | ']' => depth -= 1,
+            _ => {}
+        }
+    }
+    expression.len()
+}
+
+/// Split the leading identifier off a statement
+fn keyword(statement: &str) -> (&str, &str) {
+    let end = statement
+        .find(|c: char| !(c.is_alphanumeric() || c == '_' || c == '$'))
+        .unwrap_or(statement.len());
+    statement.split_at(end)
+}
+
+const fn is_identifier_byte(c: u8) -> bool {
+    c.is_ascii_alphanumeric() || c == b'_' || c == b'$' || c >= 0x80
+}
@@ -0,0 +1,24 @@
+use super::specifier::exported_name;
+use crate::types::ExportInfo;
+use std::collections::HashSet;
+
+/// Keep only the exports a consumer uses, like webpack's `usedExports`
+/// Star exports provide unknown names and are kept, as are type exports when
+/// `keep_types` is set and any export whose source has side effects
+pub fn prune_exports(
+    exports: Vec<ExportInfo>,
+    used: &HashSet<String>,
+    keep_types: bool,
+    has_side_effects: impl Fn(&str) -> bool,
+) -> Vec<ExportInfo> {
+    exports
+        .into_iter()
+        .filter(|exp| {
+            exported_name(exp).is_none_or(|name| {
+                used.contains(name)
+                    || (keep_types && exp.is_type_export)
+                    || has_side_effects(&exp.source)
+            })
+        })
+        .collect()
+}
@@ -1,27 +1,35 @@
 use crate::types::ExportInfo;
 use std::collections::HashMap;
 
-/// Group exports by source and type (value vs type exports)
-/// Returns a `HashMap` where:
-/// - Key: source path
-/// - Value: (`value_exports`, `type_exports`)
+/// Exports that are emitted together as one or more statements
+/// All members share a source, an export kind and the type flag
+#[derive(Debug, Clone)]
+pub struct ExportGroup {
+    pub source: String,
+    pub is_type_export: bool,
+    pub exports: Vec<ExportInfo>,
+}
+
+/// Group exports by source, kind and type flag (value vs type exports)
+/// Groups keep the order in which their first export appears, so callers
+/// control the emitted statement order by ordering the input
 #[must_use]
-pub fn group_exports_by_source(
-    exports: Vec<ExportInfo>,
-) -> HashMap<String, (Vec<ExportInfo>, Vec<ExportInfo>)> {
-    let mut source_map: HashMap<String, (Vec<ExportInfo>, Vec<ExportInfo>)> = HashMap::new();
+pub fn group_exports_by_source(exports: Vec<ExportInfo>) -> Vec<ExportGroup> {
+    let mut groups: Vec<ExportGroup> = Vec::new();
+    let mut indices: HashMap<(String, String, bool), usize> = HashMap::new();
 
     for exp in exports {
-        let key = exp.source.clone();
-        let entry = source_map
-            .entry(key)
-            .or_insert_with(|| (Vec::new(), Vec::new()));
-        if exp.is_type_export {
-            entry.1.push(exp);
-        } else {
-            entry.0.push(exp);
-        }
+        let key = (exp.source.clone(), exp.export_type.clone(), exp.is_type_export);
+        let index = *indices.entry(key).or_insert_with(|| {
+            groups.push(ExportGroup {
+                source: exp.source.clone(),
+                is_type_export: exp.is_type_export,
+                exports: Vec::new(),
+            });
+            groups.len() - 1
+        });
+        groups[index].exports.push(exp);
     }
 
-    source_map
+    groups
 }
@@ -28,18 +28,16 @@ pub fn reconstruct_source(original_source: &str, exports: Vec<ExportInfo>) -> St
         }
     }
 
-    // Group exports by source and type
-    let source_map = group_exports_by_source(exports);
+    // Group exports by source, kind and type, keeping input order
+    let groups = group_exports_by_source(exports);
 
     // Generate reconstructed exports
-    for (source, (value_exports, type_exports)) in source_map {
-        // Generate value exports
-        let value_lines = generate_value_exports(&value_exports, &source);
-        lines.extend(value_lines);
-
-        // Generate type exports
-        let type_lines = generate_type_exports(&type_exports, &source);
-        lines.extend(type_lines);
+    for group in groups {
+        if group.is_type_export {
+            lines.extend(generate_type_exports(&group.exports, &group.source));
+        } else {
+            lines.extend(generate_value_exports(&group.exports, &group.source));
+        }
     }
 
     if !lines.is_empty() {
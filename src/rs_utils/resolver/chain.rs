@@ -0,0 +1,92 @@
+use super::super::file_system::FileSystem;
+use super::Resolver;
+use crate::types::{CycleStep, ExportCycle, ExportInfo};
+use std::path::{Path, PathBuf};
+
+/// Files currently being resolved by canonical path, outermost first
+/// Each frame remembers the statement being followed out of it, so a file that
+/// shows up twice on the chain is a real cycle while a file reached through two
+/// sibling paths (a diamond) is not
+#[derive(Debug, Default)]
+pub(super) struct Chain {
+    frames: Vec<Frame>,
+}
+
+#[derive(Debug)]
+struct Frame {
+    file: PathBuf,
+    statement: Option<(u32, String)>,
+}
+
+impl Chain {
+    pub(super) fn new(root: &Path) -> Self {
+        let mut chain = Self::default();
+        chain.push(root);
+        chain
+    }
+
+    pub(super) fn push(&mut self, file: &Path) {
+        self.frames
+            .push(Frame { file: file.to_path_buf(), statement: None });
+    }
+
+    pub(super) fn pop(&mut self) {
+        self.frames.pop();
+    }
+
+    /// Record the statement of the innermost file that is about to be followed
+    pub(super) fn follow(&mut self, exp: &ExportInfo) {
+        if let Some(frame) = self.frames.last_mut() {
+            frame.statement = Some((exp.line, exp.source.clone()));
+        }
+    }
+
+    /// The cycle closed by following the current statement back into `file`
+    pub(super) fn cycle_to(&self, file: &Path) -> Option<ExportCycle> {
+        let start = self.frames.iter().position(|frame| frame.file == file)?;
+        let steps = self.frames[start..]
+            .iter()
+            .map(|frame| {
+                let (line, source) = frame.statement.clone().unwrap_or_default();
+                CycleStep { file: frame.file.to_string_lossy().into_owned(), line, source }
+            })
+            .collect();
+        Some(ExportCycle { steps })
+    }
+}
+
+impl<F: FileSystem> Resolver<F> {
+    /// Whether entering `file` would loop back into the chain
+    /// The closed cycle is recorded once, however many lookups run into it
+    pub(super) fn closes_cycle(&self, chain: &Chain, file: &Path) -> bool {
+        let Some(cycle) = chain.cycle_to(&self.canonical(file)) else {
+            return false;
+        };
+        let mut cycles = self.cycles.borrow_mut();
+        if !cycles.iter().any(|known| same_cycle(known, &cycle)) {
+            cycles.push(cycle);
+        }
+        true
+    }
+}
+
+/// True when two cycles visit the same statements, whichever file they start at
+pub(super) fn same_cycle(a: &ExportCycle, b: &ExportCycle) -> bool {
+    let len = a.steps.len();
+    len == b.steps.len()
+        && (0..len).any(|offset| (0..len).all(|i| a.steps[(i + offset) % len] == b.steps[i]))
+}
+
+/// Human-readable cycle such as `a.ts:1 -> b.ts:2 -> a.ts`
+#[must_use]
+pub fn describe_cycle(cycle: &ExportCycle) -> String {
+    let mut parts: Vec<String> = cycle
+        .steps
+        .iter()
+        .map(|step| format!("{}:{}", step.file, step.line))
+        .collect();
+    if let Some(first) = cycle.steps.first() {
+        parts.push(first.file.clone());
+    }
+    parts.join(" -> ")
+}
@@ -0,0 +1,219 @@
+use super::super::file_system::FileSystem;
+use super::super::specifier::exported_name;
+use super::chain::Chain;
+use super::{ModuleInfo, Resolver};
+use crate::types::{ExportConflict, ExportInfo};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+/// A name a module provides to `export * from`
+#[derive(Debug, Clone)]
+pub(super) struct StarName {
+    pub(super) name: String,
+    is_type: bool,
+    /// Module that declares the binding, used to tell real ambiguity apart
+    /// from the same binding reaching a barrel through two paths
+    pub(super) origin: PathBuf,
+}
+
+impl<F: FileSystem> Resolver<F> {
+    /// Replace `export * from` statements with explicit named and type exports
+    /// Names declared or re-exported explicitly by the barrel shadow star exports,
+    /// names provided by two different star exports are dropped, and stars whose
+    /// target cannot be analyzed are kept as they are
+    #[must_use]
+    pub fn expand_namespace_exports(
+        &self,
+        barrel: &Path,
+        exports: &[ExportInfo],
+    ) -> Vec<ExportInfo> {
+        let expansions =
+            filter_star_names(self.star_expansions(barrel, exports), &shadowed(exports));
+
+        exports
+            .iter()
+            .zip(expansions)
+            .flat_map(|(exp, names)| {
+                names.map_or_else(
+                    || vec![exp.clone()],
+                    |names| {
+                        names
+                            .into_iter()
+                            .map(|star| ExportInfo {
+                                specifier: star.name,
+                                source: exp.source.clone(),
+                                export_type: "named".to_string(),
+                                is_type_export: star.is_type || exp.is_type_export,
+                                line: exp.line,
+                            })
+                            .collect()
+                    },
+                )
+            })
+            .collect()
+    }
+
+    /// Names that two of the barrel's own `export *` provide from different modules
+    /// Each origin is the name as a named export from one of the stars, at its line
+    #[must_use]
+    pub fn star_conflicts(&self, barrel: &Path, exports: &[ExportInfo]) -> Vec<ExportConflict> {
+        let shadowed = shadowed(exports);
+        let mut conflicts: Vec<ExportConflict> = Vec::new();
+        let mut origins: HashMap<String, Vec<PathBuf>> = HashMap::new();
+        for (exp, names) in exports.iter().zip(self.star_expansions(barrel, exports)) {
+            for star in names.into_iter().flatten() {
+                if shadowed.contains(&star.name) {
+                    continue;
+                }
+                let seen = origins.entry(star.name.clone()).or_default();
+                if seen.contains(&star.origin) {
+                    continue;
+                }
+                seen.push(star.origin);
+                let origin = ExportInfo {
+                    specifier: star.name.clone(),
+                    export_type: "named".to_string(),
+                    is_type_export: star.is_type || exp.is_type_export,
+                    ..exp.clone()
+                };
+                if let Some(conflict) = conflicts.iter_mut().find(|c| c.name == star.name) {
+                    conflict.origins.push(origin);
+                } else {
+                    conflicts.push(ExportConflict { name: star.name, origins: vec![origin] });
+                }
+            }
+        }
+        conflicts.retain(|c| c.origins.len() > 1);
+        conflicts
+    }
+
+    /// Names each of the barrel's own `export *` provides, `None` for other exports
+    /// and for stars that cannot be analyzed
+    fn star_expansions(&self, barrel: &Path, exports: &[ExportInfo]) -> Vec<Option<Vec<StarName>>> {
+        exports
+            .iter()
+            .map(|exp| {
+                if exported_name(exp).is_some() {
+                    return None;
+                }
+                let mut chain = Chain::new(&self.canonical(barrel));
+                chain.follow(exp);
+                let target = self.resolve_source(barrel, &exp.source)?;
+                self.star_names(&target, &mut chain)
+            })
+            .collect()
+    }
+
+    /// Every name a module provides to `export * from`
+    /// Returns `None` when the module or one of its star targets cannot be analyzed
+    pub(super) fn star_names(&self, path: &Path, chain: &mut Chain) -> Option<Vec<StarName>> {
+        // Star exports that loop back contribute nothing new
+        if self.closes_cycle(chain, path) {
+            return Some(Vec::new());
+        }
+        let module = self.load(path).ok()?;
+        if !module.is_analyzable {
+            return None;
+        }
+
+        chain.push(&self.canonical(path));
+        let names = self.collect_star_names(path, &module, chain);
+        chain.pop();
+        names
+    }
+
+    fn collect_star_names(
+        &self,
+        path: &Path,
+        module: &ModuleInfo,
+        chain: &mut Chain,
+    ) -> Option<Vec<StarName>> {
+        let mut own: Vec<StarName> = module
+            .declarations
+            .iter()
+            .filter(|d| d.name != "default")
+            .map(|d| StarName {
+                name: d.name.clone(),
+                is_type: d.is_type,
+                origin: path.to_path_buf(),
+            })
+            .collect();
+        let mut stars: Vec<Option<Vec<StarName>>> = Vec::new();
+
+        for exp in &module.exports {
+            match exported_name(exp) {
+                // `export *` never forwards the default export
+                Some("default") => {}
+                Some(name) => {
+                    let resolved = self.resolve_entry(exp, path, chain).pop()?;
+                    own.push(StarName {
+                        name: name.to_string(),
+                        is_type: resolved.export.is_type_export,
+                        origin: resolved.file.unwrap_or_else(|| path.to_path_buf()),
+                    });
+                }
+                None => {
+                    chain.follow(exp);
+                    let target = self.resolve_source(path, &exp.source)?;
+                    let mut names = self.star_names(&target, chain)?;
+                    for star in &mut names {
+                        star.is_type |= exp.is_type_export;
+                    }
+                    stars.push(Some(names));
+                }
+            }
+        }
+
+        let shadowed: HashSet<String> = own.iter().map(|s| s.name.clone()).collect();
+        own.extend(
+            filter_star_names(stars, &shadowed)
+                .into_iter()
+                .flatten()
+                .flatten(),
+        );
+        Some(own)
+    }
+}
+
+/// Names the barrel exports explicitly, which shadow its star exports
+fn shadowed(exports: &[ExportInfo]) -> HashSet<String> {
+    exports
+        .iter()
+        .filter_map(exported_name)
+        .map(str::to_string)
+        .collect()
+}
+
+/// Drop shadowed and ambiguous names from the expansions of sibling star exports
+/// A name reached through several stars is kept once when every path leads to
+/// the same module and dropped when the modules differ
+fn filter_star_names(
+    groups: Vec<Option<Vec<StarName>>>,
+    shadowed: &HashSet<String>,
+) -> Vec<Option<Vec<StarName>>> {
+    let mut origins: HashMap<&str, &Path> = HashMap::new();
+    let mut ambiguous: HashSet<String> = HashSet::new();
+    for star in groups.iter().flatten().flatten() {
+        let origin = origins.entry(&star.name).or_insert(&star.origin);
+        if *origin != star.origin {
+            ambiguous.insert(star.name.clone());
+        }
+    }
+
+    let mut emitted: HashSet<String> = HashSet::new();
+    groups
+        .into_iter()
+        .map(|group| {
+            group.map(|names| {
+                names
+                    .into_iter()
+                    .filter(|star| {
+                        !shadowed.contains(&star.name)
+                            && !ambiguous.contains(&star.name)
+                            && emitted.insert(star.name.clone())
+                    })
+                    .collect()
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,227 @@
+use super::super::file_system::FileSystem;
+use super::super::specifier::{exported_name, join_specifier, split_specifier};
+use super::chain::Chain;
+use super::{ModuleInfo, ResolvedExport, Resolver};
+use crate::types::ExportInfo;
+use std::path::{Path, PathBuf};
+
+/// Outcome of looking up an export name in a module
+pub(super) enum Lookup {
+    /// The binding was traced to its leaf module
+    Found(ResolvedExport),
+    /// The module cannot provide the name
+    Missing,
+    /// The module may declare the name itself
+    Unknown,
+}
+
+impl<F: FileSystem> Resolver<F> {
+    /// Resolve one re-export statement of `importer`
+    pub(super) fn resolve_entry(
+        &self,
+        exp: &ExportInfo,
+        importer: &Path,
+        chain: &mut Chain,
+    ) -> Vec<ResolvedExport> {
+        chain.follow(exp);
+        let Some(target) = self.resolve_source(importer, &exp.source) else {
+            return vec![ResolvedExport {
+                export: exp.clone(),
+                file: None,
+                via: Vec::new(),
+                from_star: false,
+            }];
+        };
+
+        match exp.export_type.as_str() {
+            "namespace" if exp.specifier == "*" => self.expand_star(exp, &target, chain),
+            "namespace" => vec![leaf(exp, exp.specifier.clone(), target)],
+            _ => {
+                let (local, exported) = split_specifier(&exp.specifier);
+                match self.find_export(&target, local, chain) {
+                    // Skipping a module with side effects would drop the code it runs
+                    Lookup::Found(found)
+                        if found.via.iter().all(|module| self.can_skip(module)) =>
+                    {
+                        vec![rename(found, exp, exported)]
+                    }
+                    Lookup::Found(_) | Lookup::Missing | Lookup::Unknown => {
+                        vec![leaf(exp, exp.specifier.clone(), target)]
+                    }
+                }
+            }
+        }
+    }
+
+    /// Expand `export * from` into the re-exports of a pure barrel
+    /// Modules with their own declarations or side effects are kept as a star
+    /// export, and stars that close a cycle are dropped; the entries keep their
+    /// star provenance so `settle_stars` applies the rules of the original star
+    fn expand_star(
+        &self,
+        exp: &ExportInfo,
+        target: &Path,
+        chain: &mut Chain,
+    ) -> Vec<ResolvedExport> {
+        // A star back into the chain adds nothing the outer statements don't provide
+        if self.closes_cycle(chain, target) {
+            return Vec::new();
+        }
+        let module = match self.load(target) {
+            Ok(module) if module.is_pure_barrel && self.can_skip(target) => module,
+            _ => return vec![leaf(exp, exp.specifier.clone(), target.to_path_buf())],
+        };
+
+        chain.push(&self.canonical(target));
+        // `export *` never forwards the default export
+        let entries: Vec<ExportInfo> = module
+            .exports
+            .iter()
+            .filter(|entry| exported_name(entry) != Some("default"))
+            .cloned()
+            .collect();
+        let groups = entries
+            .iter()
+            .map(|entry| self.resolve_entry(entry, target, chain))
+            .collect();
+        chain.pop();
+
+        self.settle_stars(target, &entries, groups)
+            .into_iter()
+            .map(|mut found| {
+                found.export.is_type_export |= exp.is_type_export;
+                found.export.line = exp.line;
+                found.via.insert(0, target.to_path_buf());
+                found.from_star = true;
+                found
+            })
+            .collect()
+    }
+
+    /// Trace a name `module_path` exports to the module declaring it
+    /// Returns `None` when the name is missing or not provided unambiguously
+    #[must_use]
+    pub fn trace_export(&self, module_path: &Path, name: &str) -> Option<ResolvedExport> {
+        match self.find_export(module_path, name, &mut Chain::default()) {
+            Lookup::Found(found) => Some(found),
+            Lookup::Missing | Lookup::Unknown => None,
+        }
+    }
+
+    /// Trace an export name of a module to the module that provides it
+    pub(super) fn find_export(&self, module_path: &Path, name: &str, chain: &mut Chain) -> Lookup {
+        if self.closes_cycle(chain, module_path) {
+            return Lookup::Unknown;
+        }
+        let Ok(module) = self.load(module_path) else {
+            return Lookup::Unknown;
+        };
+
+        chain.push(&self.canonical(module_path));
+        let lookup = self.find_in_module(module_path, &module, name, chain);
+        chain.pop();
+
+        match lookup {
+            Lookup::Found(mut found) if found.file.as_deref() != Some(module_path) => {
+                found.via.insert(0, module_path.to_path_buf());
+                Lookup::Found(found)
+            }
+            Lookup::Missing if !module.is_analyzable => Lookup::Unknown,
+            other => other,
+        }
+    }
+
+    fn find_in_module(
+        &self,
+        module_path: &Path,
+        module: &ModuleInfo,
+        name: &str,
+        chain: &mut Chain,
+    ) -> Lookup {
+        if let Some(entry) = module
+            .exports
+            .iter()
+            .find(|e| exported_name(e) == Some(name))
+        {
+            let mut resolved = self.resolve_entry(entry, module_path, chain);
+            return resolved.pop().map_or(Lookup::Unknown, Lookup::Found);
+        }
+
+        if let Some(declaration) = module.declaration(name) {
+            return Lookup::Found(declared(module_path, name, declaration.is_type));
+        }
+
+        if name == "default" {
+            return Lookup::Missing;
+        }
+
+        // Search star re-exports; the name is only traced when exactly one provides it
+        let mut found: Option<ResolvedExport> = None;
+        for entry in module.exports.iter().filter(|e| exported_name(e).is_none()) {
+            chain.follow(entry);
+            let Some(target) = self.resolve_source(module_path, &entry.source) else {
+                return Lookup::Unknown;
+            };
+            match self.find_export(&target, name, chain) {
+                Lookup::Found(mut hit) if found.is_none() => {
+                    hit.export.is_type_export |= entry.is_type_export;
+                    found = Some(hit);
+                }
+                Lookup::Missing => {}
+                Lookup::Found(_) | Lookup::Unknown => return Lookup::Unknown,
+            }
+        }
+
+        found.map_or(Lookup::Missing, Lookup::Found)
+    }
+}
+
+/// Point at a binding declared by the module itself
+fn declared(module_path: &Path, name: &str, is_type: bool) -> ResolvedExport {
+    ResolvedExport {
+        export: ExportInfo {
+            specifier: name.to_string(),
+            source: module_path.to_string_lossy().into_owned(),
+            export_type: "named".to_string(),
+            is_type_export: is_type,
+            line: 0,
+        },
+        file: Some(module_path.to_path_buf()),
+        via: Vec::new(),
+        from_star: false,
+    }
+}
+
+/// Point an export at a module without following it any further
+pub(super) fn leaf(exp: &ExportInfo, specifier: String, file: PathBuf) -> ResolvedExport {
+    ResolvedExport {
+        export: ExportInfo {
+            specifier,
+            source: file.to_string_lossy().into_owned(),
+            ..exp.clone()
+        },
+        file: Some(file),
+        via: Vec::new(),
+        from_star: false,
+    }
+}
+
+/// Re-expose a traced binding under the name the importing statement exports
+fn rename(found: ResolvedExport, exp: &ExportInfo, exported: &str) -> ResolvedExport {
+    let specifier = match found.export.export_type.as_str() {
+        "namespace" => exported.to_string(),
+        _ => join_specifier(split_specifier(&found.export.specifier).0, exported),
+    };
+    ResolvedExport {
+        export: ExportInfo {
+            specifier,
+            export_type: found.export.export_type,
+            source: found.export.source,
+            is_type_export: found.export.is_type_export || exp.is_type_export,
+            line: exp.line,
+        },
+        file: found.file,
+        via: found.via,
+        from_star: false,
+    }
+}
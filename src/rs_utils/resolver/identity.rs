@@ -0,0 +1,45 @@
+use super::super::file_system::FileSystem;
+use super::paths::normalize_path;
+use super::{ResolvedExport, Resolver};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+impl<F: FileSystem> Resolver<F> {
+    /// Real path of a module, following symlinks such as pnpm's `node_modules` links
+    /// Paths the filesystem cannot resolve are only normalized
+    #[must_use]
+    pub fn canonical(&self, path: &Path) -> PathBuf {
+        if let Some(real) = self.realpaths.borrow().get(path) {
+            return real.clone();
+        }
+        let real = self
+            .fs
+            .realpath(path)
+            .unwrap_or_else(|_| normalize_path(path));
+        self.realpaths
+            .borrow_mut()
+            .insert(path.to_path_buf(), real.clone());
+        real
+    }
+
+    /// Drop exports that reach the same binding of the same module
+    /// Modules are compared by real path, so symlinked copies count once
+    #[must_use]
+    pub fn dedupe_by_identity(&self, resolved: Vec<ResolvedExport>) -> Vec<ResolvedExport> {
+        let mut seen = HashSet::new();
+        resolved
+            .into_iter()
+            .filter(|entry| {
+                let Some(file) = &entry.file else {
+                    return true;
+                };
+                seen.insert((
+                    self.canonical(file),
+                    entry.export.specifier.clone(),
+                    entry.export.export_type.clone(),
+                    entry.export.is_type_export,
+                ))
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,248 @@
+mod chain;
+mod expand;
+mod flatten;
+mod identity;
+mod module_info;
+mod options;
+mod packages;
+mod paths;
+mod prefetch;
+mod probe;
+mod rewrite;
+mod side_effects;
+mod stars;
+
+pub use chain::describe_cycle;
+pub use module_info::ModuleInfo;
+pub use options::{ModuleResolution, ResolveOptions, SourceStyle};
+pub use paths::{is_relative_specifier, normalize_path};
+use probe::{resolve_path, resolve_relative};
+
+use super::file_system::{FileSystem, OsFileSystem};
+use super::import_map::{is_url_specifier, ImportMap, MappedSpecifier};
+use super::module_cache::ModuleCache;
+use super::package_json::PackageJson;
+use super::tsconfig::TsConfig;
+use crate::types::{ExportCycle, ExportInfo, ModuleSideEffects};
+use chain::Chain;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+/// An export after following re-export chains
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResolvedExport {
+    /// Export pointing at the module that provides the binding
+    pub export: ExportInfo,
+    /// Resolved leaf module, or `None` when the source could not be resolved
+    pub file: Option<PathBuf>,
+    /// Modules the binding was re-exported through on the way to `file`,
+    /// starting with the one the lookup began in
+    pub via: Vec<PathBuf>,
+    /// Flattened out of an `export *`, so explicit exports shadow it and another
+    /// star providing the name from a different module makes it ambiguous
+    pub from_star: bool,
+}
+
+/// Follows re-export chains from barrel files down to leaf modules
+/// Parsed modules are cached; every read goes through the resolver's filesystem
+#[derive(Debug)]
+pub struct Resolver<F = OsFileSystem> {
+    fs: F,
+    /// Parsed modules keyed by canonical (symlink-free) path
+    modules: RefCell<HashMap<PathBuf, Rc<ModuleInfo>>>,
+    realpaths: RefCell<HashMap<PathBuf, PathBuf>>,
+    cache: Option<ModuleCache>,
+    packages: RefCell<HashMap<PathBuf, Rc<PackageJson>>>,
+    side_effects: RefCell<HashMap<PathBuf, ModuleSideEffects>>,
+    tsconfig: Option<TsConfig>,
+    import_maps: Vec<ImportMap>,
+    resolve_packages: bool,
+    symlinks: bool,
+    /// Stop following re-exports at modules with side effects
+    side_effect_barriers: bool,
+    options: ResolveOptions,
+    cycles: RefCell<Vec<ExportCycle>>,
+}
+
+impl Resolver {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<F: FileSystem + Default> Default for Resolver<F> {
+    fn default() -> Self {
+        Self::with_file_system(F::default())
+    }
+}
+
+impl<F: FileSystem> Resolver<F> {
+    /// Resolve through a custom filesystem instead of the disk
+    #[must_use]
+    pub fn with_file_system(fs: F) -> Self {
+        Self {
+            fs,
+            modules: RefCell::default(),
+            realpaths: RefCell::default(),
+            cache: None,
+            packages: RefCell::default(),
+            side_effects: RefCell::default(),
+            tsconfig: None,
+            import_maps: Vec::new(),
+            resolve_packages: false,
+            symlinks: true,
+            side_effect_barriers: true,
+            options: ResolveOptions::default(),
+            cycles: RefCell::default(),
+        }
+    }
+
+    /// The filesystem modules are read from
+    pub const fn file_system(&self) -> &F {
+        &self.fs
+    }
+
+    /// Resolve aliased specifiers through tsconfig `paths` and `baseUrl`
+    #[must_use]
+    pub fn with_tsconfig(mut self, tsconfig: TsConfig) -> Self {
+        self.tsconfig = Some(tsconfig);
+        self
+    }
+
+    /// Read a barrel and flatten its exports
+    pub fn resolve_barrel(&self, barrel: &Path) -> Result<Vec<ResolvedExport>, String> {
+        let module: Rc<ModuleInfo> = self.load(barrel)?;
+        Ok(self.resolve_exports(barrel, &module.exports))
+    }
+
+    /// Flatten exports already parsed from a barrel
+    #[must_use]
+    pub fn resolve_exports(&self, barrel: &Path, exports: &[ExportInfo]) -> Vec<ResolvedExport> {
+        let mut chain = Chain::new(&self.canonical(barrel));
+        let groups = exports
+            .iter()
+            .map(|exp| self.resolve_entry(exp, barrel, &mut chain))
+            .collect();
+        self.settle_stars(barrel, exports, groups)
+    }
+
+    /// Re-export cycles met so far, in the order they were found
+    /// Diamonds, where one module is reached through two sibling paths, are not cycles
+    #[must_use]
+    pub fn cycles(&self) -> Vec<ExportCycle> {
+        self.cycles.borrow().clone()
+    }
+
+    /// Resolve an import specifier relative to the importing file
+    /// Symlinks are resolved to the real path unless disabled with `with_symlinks`
+    #[must_use]
+    pub fn resolve_source(&self, importer: &Path, specifier: &str) -> Option<PathBuf> {
+        let resolved = self.resolve_specifier(importer, specifier)?;
+        Some(if self.symlinks {
+            self.canonical(&resolved)
+        } else {
+            resolved
+        })
+    }
+
+    fn resolve_specifier(&self, importer: &Path, specifier: &str) -> Option<PathBuf> {
+        if is_url_specifier(specifier) {
+            return None;
+        }
+        if let Some(mapped) = self
+            .import_maps
+            .iter()
+            .find_map(|map| map.resolve(importer, specifier))
+        {
+            return match mapped {
+                MappedSpecifier::Path(path) => resolve_path(&self.fs, &path, &self.options),
+                MappedSpecifier::Url(_) => None,
+            };
+        }
+        if is_relative_specifier(specifier) {
+            return resolve_relative(&self.fs, importer, specifier, &self.options);
+        }
+        if specifier.starts_with('#') {
+            return self.resolve_package_import(importer, specifier);
+        }
+        let aliased = self.tsconfig.as_ref().and_then(|tsconfig| {
+            tsconfig
+                .resolve_alias(specifier)
+                .iter()
+                .find_map(|candidate| resolve_path(&self.fs, candidate, &self.options))
+        });
+        if aliased.is_some() || !self.resolve_packages {
+            return aliased;
+        }
+        self.resolve_package(importer, specifier)
+    }
+
+    /// Map specifiers through import maps before probing files
+    /// Earlier maps take precedence over later ones
+    #[must_use]
+    pub fn with_import_maps(mut self, import_maps: Vec<ImportMap>) -> Self {
+        self.import_maps = import_maps;
+        self
+    }
+
+    /// Replace the extension, main file, condition and main field settings
+    #[must_use]
+    pub fn with_options(mut self, options: ResolveOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Keep resolved paths as the real file (`true`, the default) or as the
+    /// symlinked path they were reached through, like webpack's `resolve.symlinks`
+    #[must_use]
+    pub const fn with_symlinks(mut self, enabled: bool) -> Self {
+        self.symlinks = enabled;
+        self
+    }
+
+    /// Stop following re-exports at modules with side effects (`true`, the default),
+    /// or trace every name to its declaration for tools that never emit code
+    #[must_use]
+    pub const fn with_side_effect_barriers(mut self, enabled: bool) -> Self {
+        self.side_effect_barriers = enabled;
+        self
+    }
+
+    /// Store parse results on disk and reuse them for unchanged files
+    #[must_use]
+    pub fn with_cache(mut self, cache: ModuleCache) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// Follow bare specifiers into packages found in `node_modules`
+    #[must_use]
+    pub const fn with_packages(mut self, enabled: bool) -> Self {
+        self.resolve_packages = enabled;
+        self
+    }
+
+    /// Modules parsed so far, keyed by canonical path
+    #[must_use]
+    pub fn modules(&self) -> Vec<(PathBuf, Rc<ModuleInfo>)> {
+        self.modules
+            .borrow()
+            .iter()
+            .map(|(path, module)| (path.clone(), Rc::clone(module)))
+            .collect()
+    }
+
+    /// Load a module, reusing the cached analysis when available
+    pub fn load(&self, path: &Path) -> Result<Rc<ModuleInfo>, String> {
+        let key = self.canonical(path);
+        if let Some(module) = self.modules.borrow().get(&key) {
+            return Ok(Rc::clone(module));
+        }
+        let module = Rc::new(module_info::load_module(&self.fs, path, self.cache.as_ref())?);
+        self.modules.borrow_mut().insert(key, Rc::clone(&module));
+        Ok(module)
+    }
+}
@@ -0,0 +1,75 @@
+use super::super::file_system::{read_file, FileSystem};
+use super::super::module_cache::ModuleCache;
+use super::super::parser::{find_side_effects, parse_exports, parse_local_exports, LocalExport};
+use crate::types::ExportInfo;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// What the resolver knows about a single module
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ModuleInfo {
+    /// Re-export statements (`export ... from`)
+    pub exports: Vec<ExportInfo>,
+    /// Names exported by the module's own declarations
+    pub declarations: Vec<LocalExport>,
+    /// True when every statement in the module is a re-export
+    /// Only such modules can be flattened without losing their own code
+    pub is_pure_barrel: bool,
+    /// True when every export statement in the module was understood,
+    /// so its full list of export names is known
+    pub is_analyzable: bool,
+    /// Lines of top-level statements that run code when the module is imported
+    pub side_effects: Vec<u32>,
+}
+
+impl ModuleInfo {
+    /// Find a declaration exported under `name`
+    #[must_use]
+    pub fn declaration(&self, name: &str) -> Option<&LocalExport> {
+        self.declarations.iter().find(|d| d.name == name)
+    }
+}
+
+/// Read and analyze a module, reusing the cached analysis of unchanged contents
+pub fn load_module<F: FileSystem + ?Sized>(
+    fs: &F,
+    path: &Path,
+    cache: Option<&ModuleCache>,
+) -> Result<ModuleInfo, String> {
+    let source: String = read_file(fs, path)?;
+    cache.map_or_else(|| analyze_module(&source), |cache| cache.analyze(&source, analyze_module))
+}
+
+/// Analyze module source code
+fn analyze_module(source: &str) -> Result<ModuleInfo, String> {
+    let exports: Vec<ExportInfo> = parse_exports(source)?;
+    let declarations: Vec<LocalExport> = parse_local_exports(source);
+    let reexport_lines: Vec<u32> = exports.iter().map(|e| e.line).collect();
+    let declaration_lines: Vec<u32> = declarations.iter().map(|d| d.line).collect();
+
+    let mut is_pure_barrel = true;
+    let mut is_analyzable = true;
+    for (index, line) in source.lines().enumerate() {
+        let trimmed = line.trim();
+        let line_number = u32::try_from(index + 1).unwrap_or(u32::MAX);
+        if trimmed.is_empty() || is_comment(trimmed) || reexport_lines.contains(&line_number) {
+            continue;
+        }
+        is_pure_barrel = false;
+        if trimmed.starts_with("export") && !declaration_lines.contains(&line_number) {
+            is_analyzable = false;
+        }
+    }
+
+    Ok(ModuleInfo {
+        exports,
+        declarations,
+        is_pure_barrel,
+        is_analyzable,
+        side_effects: find_side_effects(source),
+    })
+}
+
+fn is_comment(line: &str) -> bool {
+    line.starts_with("//") || line.starts_with("/*") || line.starts_with('*')
+}
@@ -0,0 +1,124 @@
+/// TypeScript `moduleResolution` modes that change how specifiers are probed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ModuleResolution {
+    /// `node10` / `node`: extensionless specifiers and index files
+    Node10,
+    /// `node16` / `nodenext`: `.js` specifiers may point at `.ts` sources
+    Node16,
+    /// `bundler`: like `node16` for extension substitution
+    Bundler,
+}
+
+impl ModuleResolution {
+    /// Parse a `moduleResolution` value, case-insensitively as tsc does
+    pub fn parse(value: &str) -> Result<Self, String> {
+        match value.to_ascii_lowercase().as_str() {
+            "node" | "node10" | "classic" => Ok(Self::Node10),
+            "node16" | "nodenext" => Ok(Self::Node16),
+            "bundler" => Ok(Self::Bundler),
+            other => Err(format!(
+                "Unknown moduleResolution \"{other}\"; expected node10, node16, nodenext or bundler"
+            )),
+        }
+    }
+
+    /// Whether TypeScript substitutes `.js` specifiers with their `.ts` sources
+    #[must_use]
+    pub const fn maps_js_to_ts(self) -> bool {
+        matches!(self, Self::Node16 | Self::Bundler)
+    }
+}
+
+/// How flattened sources are written relative to the barrel being processed
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SourceStyle {
+    /// Shortest relative specifier that resolves back: `./ui/button`, `./ui` for `ui/index.ts`
+    #[default]
+    Relative,
+    /// Relative path to the file without its extension: `./ui/index`
+    Extensionless,
+    /// Relative path including the extension: `./ui/button.tsx`
+    Extension,
+    /// tsconfig `paths`/`baseUrl` alias when one maps to the file, else `Relative`
+    Alias,
+    /// Relative path with the runtime extension, as Node's ESM loader needs:
+    /// `./ui/button.js` for `button.ts`, `./ui/index.js` for a directory
+    FullySpecified,
+    /// The resolved file path
+    Absolute,
+}
+
+impl SourceStyle {
+    pub fn parse(value: &str) -> Result<Self, String> {
+        match value {
+            "relative" => Ok(Self::Relative),
+            "extensionless" => Ok(Self::Extensionless),
+            "extension" => Ok(Self::Extension),
+            "alias" => Ok(Self::Alias),
+            "fully-specified" => Ok(Self::FullySpecified),
+            "absolute" => Ok(Self::Absolute),
+            other => Err(format!(
+                "Unknown sourceStyle \"{other}\"; expected relative, extensionless, extension, \
+                 alias, fully-specified or absolute"
+            )),
+        }
+    }
+}
+
+/// File probing and package entry settings, mirroring webpack's `resolve` config
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResolveOptions {
+    /// Extensions appended to extensionless paths, in priority order (`resolve.extensions`)
+    pub extensions: Vec<String>,
+    /// File names tried inside directories (`resolve.mainFiles`)
+    pub main_files: Vec<String>,
+    /// Conditions matched in `exports`/`imports`, besides `default` (`resolve.conditionNames`)
+    pub condition_names: Vec<String>,
+    /// `package.json` fields used when a package has no `exports` (`resolve.mainFields`)
+    pub main_fields: Vec<String>,
+    /// Extensions replaced by alternatives before probing (`resolve.extensionAlias`)
+    pub extension_alias: Vec<(String, Vec<String>)>,
+}
+
+impl Default for ResolveOptions {
+    fn default() -> Self {
+        Self {
+            extensions: to_strings(&[".ts", ".tsx", ".js", ".jsx", ".mjs", ".mts", ".cjs", ".cts"]),
+            main_files: to_strings(&["index"]),
+            condition_names: to_strings(&["import", "module"]),
+            main_fields: to_strings(&["module", "main", "types"]),
+            extension_alias: Vec::new(),
+        }
+    }
+}
+
+impl ResolveOptions {
+    /// Add TypeScript's output-to-source extension mapping when the mode uses it
+    /// `./button.js` then finds `button.ts` or `button.tsx` before `button.js`
+    #[must_use]
+    pub fn with_module_resolution(mut self, mode: ModuleResolution) -> Self {
+        if mode.maps_js_to_ts() {
+            self.extension_alias = vec![
+                (".js".to_string(), to_strings(&[".ts", ".tsx", ".js"])),
+                (".jsx".to_string(), to_strings(&[".tsx", ".jsx"])),
+                (".mjs".to_string(), to_strings(&[".mts", ".mjs"])),
+                (".cjs".to_string(), to_strings(&[".cts", ".cjs"])),
+            ];
+        }
+        self
+    }
+
+    /// Replacement extensions for a file name, if its extension is aliased
+    pub(super) fn aliases_for<'a>(&'a self, file_name: &'a str) -> Option<(&'a str, &'a [String])> {
+        self.extension_alias.iter().find_map(|(ext, aliases)| {
+            file_name
+                .strip_suffix(ext.as_str())
+                .filter(|stem| !stem.is_empty())
+                .map(|stem| (stem, aliases.as_slice()))
+        })
+    }
+}
+
+fn to_strings(values: &[&str]) -> Vec<String> {
+    values.iter().map(|value| (*value).to_string()).collect()
+}
@@ -0,0 +1,61 @@
+use super::super::file_system::FileSystem;
+use super::super::package_json::{
+    find_package_dir, find_package_scope, resolve_package_target, split_package_specifier,
+    PackageJson,
+};
+use super::probe::resolve_path;
+use super::Resolver;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+impl<F: FileSystem> Resolver<F> {
+    /// Resolve a bare specifier to a file inside a package found in `node_modules`
+    pub(super) fn resolve_package(&self, importer: &Path, specifier: &str) -> Option<PathBuf> {
+        let (name, subpath) = split_package_specifier(specifier)?;
+        let dir = find_package_dir(&self.fs, importer.parent()?, name)?;
+        let package = self.load_package(&dir)?;
+        let target = package.resolve_subpath(
+            &subpath,
+            &self.options.condition_names,
+            &self.options.main_fields,
+        )?;
+        resolve_path(&self.fs, &target, &self.options)
+    }
+
+    /// Resolve a `#` specifier through the `imports` field of the importer's package
+    /// Targets are paths inside the package, or package specifiers that are only
+    /// followed when package resolution is enabled
+    pub(super) fn resolve_package_import(
+        &self,
+        importer: &Path,
+        specifier: &str,
+    ) -> Option<PathBuf> {
+        let dir = find_package_scope(&self.fs, importer.parent()?)?;
+        let package = self.load_package(&dir)?;
+        let target = resolve_package_target(
+            package.imports.as_ref()?,
+            specifier,
+            &self.options.condition_names,
+        )?;
+
+        if target.starts_with("./") {
+            return resolve_path(&self.fs, &dir.join(target), &self.options);
+        }
+        if !self.resolve_packages {
+            return None;
+        }
+        self.resolve_package(&dir.join("package.json"), &target)
+    }
+
+    /// Load a `package.json`, reusing the cached copy when available
+    pub(super) fn load_package(&self, dir: &Path) -> Option<Rc<PackageJson>> {
+        if let Some(package) = self.packages.borrow().get(dir) {
+            return Some(Rc::clone(package));
+        }
+        let package = Rc::new(PackageJson::load(&self.fs, dir).ok()?);
+        self.packages
+            .borrow_mut()
+            .insert(dir.to_path_buf(), Rc::clone(&package));
+        Some(package)
+    }
+}
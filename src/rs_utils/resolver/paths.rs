@@ -0,0 +1,56 @@
+use std::path::{Component, Path, PathBuf};
+
+/// Normalize `.` and `..` components without touching the filesystem
+#[must_use]
+pub fn normalize_path(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                // Leading `..` segments of a relative path cannot be collapsed
+                let stuck = matches!(
+                    normalized.components().next_back(),
+                    None | Some(Component::ParentDir)
+                );
+                if stuck || !normalized.pop() {
+                    normalized.push("..");
+                }
+            }
+            other => normalized.push(other.as_os_str()),
+        }
+    }
+    normalized
+}
+
+/// Relative specifier from a directory to a path, always starting with `./` or `../`
+#[must_use]
+pub fn relative_specifier(from_dir: &Path, to: &Path) -> String {
+    let from = normalize_path(from_dir);
+    let to = normalize_path(to);
+    let from: Vec<Component> = from.components().collect();
+    let target: Vec<Component> = to.components().collect();
+    let common = from.iter().zip(&target).take_while(|(a, b)| a == b).count();
+
+    let mut parts: Vec<String> = vec!["..".to_string(); from.len() - common];
+    parts.extend(
+        target[common..]
+            .iter()
+            .map(|part| part.as_os_str().to_string_lossy().into_owned()),
+    );
+    match parts.first().map(String::as_str) {
+        None => ".".to_string(),
+        Some("..") => parts.join("/"),
+        Some(_) => format!("./{}", parts.join("/")),
+    }
+}
+
+/// Check if an import specifier points at a file path rather than a package
+#[must_use]
+pub fn is_relative_specifier(specifier: &str) -> bool {
+    specifier == "."
+        || specifier == ".."
+        || specifier.starts_with("./")
+        || specifier.starts_with("../")
+        || specifier.starts_with('/')
+}
@@ -0,0 +1,189 @@
+use super::super::file_system::FileSystem;
+use super::super::import_map::ImportMap;
+use super::super::module_cache::ModuleCache;
+use super::super::tsconfig::TsConfig;
+use super::{module_info, ModuleInfo, ResolveOptions, Resolver};
+use crate::types::ExportInfo;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+/// Resolution settings shared with worker threads, which build their own resolver
+struct Settings {
+    tsconfig: Option<TsConfig>,
+    import_maps: Vec<ImportMap>,
+    options: ResolveOptions,
+    cache: Option<ModuleCache>,
+    resolve_packages: bool,
+    symlinks: bool,
+    side_effect_barriers: bool,
+}
+
+/// A module of the graph and the modules its re-exports resolve to
+type Visited = (PathBuf, Option<ModuleInfo>, Vec<PathBuf>);
+
+impl<F: FileSystem> Resolver<F> {
+    /// Read, parse and resolve every module reachable from a barrel's re-exports
+    /// ahead of resolution, on up to `threads` threads, one level of the graph at a time
+    /// Like flattening, the walk does not go past modules with side effects;
+    /// resolution then runs on the warm cache, so its results and order are unchanged
+    pub fn prefetch(&self, barrel: &Path, exports: &[ExportInfo], threads: usize) {
+        let Some(fs) = self.fs.as_sync().filter(|_| threads > 1) else {
+            return;
+        };
+        let settings = self.settings();
+        let sources: Vec<&str> = exports.iter().map(|exp| exp.source.as_str()).collect();
+        let mut seen: HashSet<PathBuf> = HashSet::new();
+        let mut frontier: Vec<PathBuf> =
+            run_parallel(fs, &settings, &sources, threads, |resolver, source| {
+                resolver
+                    .resolve_source(barrel, source)
+                    .map(|target| resolver.canonical(&target))
+            })
+            .into_iter()
+            .flatten()
+            .filter(|target| seen.insert(target.clone()))
+            .collect();
+
+        while !frontier.is_empty() {
+            // Modules parsed before are handed to the workers so only their
+            // re-exports are resolved again
+            let pending: Vec<(PathBuf, Option<ModuleInfo>)> = frontier
+                .into_iter()
+                .map(|path| {
+                    let module = self.modules.borrow().get(&path).map(|m| (**m).clone());
+                    (path, module)
+                })
+                .collect();
+            let visited =
+                run_parallel(fs, &settings, &pending, threads, |resolver, (path, module)| {
+                    visit(resolver, path, module.clone())
+                });
+
+            frontier = Vec::new();
+            for (path, module, targets) in visited.into_iter().flatten() {
+                if let Some(module) = module {
+                    self.modules.borrow_mut().insert(path, Rc::new(module));
+                }
+                frontier.extend(
+                    targets
+                        .into_iter()
+                        .filter(|target| seen.insert(target.clone())),
+                );
+            }
+        }
+    }
+
+    fn settings(&self) -> Settings {
+        Settings {
+            tsconfig: self.tsconfig.clone(),
+            import_maps: self.import_maps.clone(),
+            options: self.options.clone(),
+            cache: self.cache.clone(),
+            resolve_packages: self.resolve_packages,
+            symlinks: self.symlinks,
+            side_effect_barriers: self.side_effect_barriers,
+        }
+    }
+}
+
+impl Settings {
+    /// A resolver for one worker thread, with caches of its own
+    fn resolver<'fs>(
+        &self,
+        fs: &'fs (dyn FileSystem + Sync),
+    ) -> Resolver<&'fs (dyn FileSystem + Sync)> {
+        let mut resolver = Resolver::with_file_system(fs)
+            .with_import_maps(self.import_maps.clone())
+            .with_options(self.options.clone())
+            .with_packages(self.resolve_packages)
+            .with_symlinks(self.symlinks)
+            .with_side_effect_barriers(self.side_effect_barriers);
+        if let Some(tsconfig) = &self.tsconfig {
+            resolver = resolver.with_tsconfig(tsconfig.clone());
+        }
+        if let Some(cache) = &self.cache {
+            resolver = resolver.with_cache(cache.clone());
+        }
+        resolver
+    }
+}
+
+/// Load a module unless it was parsed before and resolve its re-exports, which
+/// are not followed past a module with side effects
+fn visit<F: FileSystem>(
+    resolver: &Resolver<F>,
+    path: &Path,
+    module: Option<ModuleInfo>,
+) -> Option<Visited> {
+    let loaded = module.is_none();
+    let module = match module {
+        Some(module) => Rc::new(module),
+        None => {
+            Rc::new(module_info::load_module(&resolver.fs, path, resolver.cache.as_ref()).ok()?)
+        }
+    };
+    resolver
+        .modules
+        .borrow_mut()
+        .insert(path.to_path_buf(), Rc::clone(&module));
+    let targets: Vec<PathBuf> = if resolver.can_skip(path) {
+        module
+            .exports
+            .iter()
+            .filter_map(|exp| resolver.resolve_source(path, &exp.source))
+            .map(|target| resolver.canonical(&target))
+            .collect()
+    } else {
+        Vec::new()
+    };
+    Some((path.to_path_buf(), loaded.then(|| (*module).clone()), targets))
+}
+
+/// Run `task` over `items` on a bounded set of scoped threads, each with its own
+/// resolver, returning the results in input order; items of a failed thread are skipped
+fn run_parallel<T, R, G>(
+    fs: &(dyn FileSystem + Sync),
+    settings: &Settings,
+    items: &[T],
+    threads: usize,
+    task: G,
+) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    G: Fn(&Resolver<&(dyn FileSystem + Sync)>, &T) -> R + Sync,
+{
+    let workers = threads.min(items.len());
+    if workers <= 1 {
+        let resolver = settings.resolver(fs);
+        return items.iter().map(|item| task(&resolver, item)).collect();
+    }
+
+    let next = AtomicUsize::new(0);
+    let mut finished: Vec<(usize, R)> = thread::scope(|scope| {
+        let handles: Vec<_> = (0..workers)
+            .map(|_| {
+                scope.spawn(|| {
+                    let resolver = settings.resolver(fs);
+                    let mut done = Vec::new();
+                    loop {
+                        let index = next.fetch_add(1, Ordering::Relaxed);
+                        let Some(item) = items.get(index) else {
+                            return done;
+                        };
+                        done.push((index, task(&resolver, item)));
+                    }
+                })
+            })
+            .collect();
+        handles
+            .into_iter()
+            .flat_map(|handle| handle.join().unwrap_or_default())
+            .collect()
+    });
+    finished.sort_by_key(|(index, _)| *index);
+    finished.into_iter().map(|(_, result)| result).collect()
+}
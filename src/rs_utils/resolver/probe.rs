@@ -0,0 +1,72 @@
+use super::super::file_system::FileSystem;
+use super::super::package_json::PackageJson;
+use super::options::ResolveOptions;
+use super::paths::normalize_path;
+use std::path::{Path, PathBuf};
+
+/// Resolve a relative specifier against the directory of the importing file
+#[must_use]
+pub fn resolve_relative<F: FileSystem>(
+    fs: &F,
+    importer: &Path,
+    specifier: &str,
+    options: &ResolveOptions,
+) -> Option<PathBuf> {
+    let base: &Path = importer.parent().unwrap_or_else(|| Path::new(""));
+    resolve_path(fs, &base.join(specifier), options)
+}
+
+/// Resolve a path that may omit its extension or point at a directory
+/// Tries the path as a file, then the directory's `package.json` main fields,
+/// then its main files, the same order webpack uses
+#[must_use]
+pub fn resolve_path<F: FileSystem>(
+    fs: &F,
+    path: &Path,
+    options: &ResolveOptions,
+) -> Option<PathBuf> {
+    let candidate: PathBuf = normalize_path(path);
+
+    if let Some(file) = probe_file(fs, &candidate, options) {
+        return Some(file);
+    }
+    if !fs.is_dir(&candidate) {
+        return None;
+    }
+
+    let main = PackageJson::load(fs, &candidate)
+        .ok()
+        .and_then(|package| package.main_entry(&options.main_fields))
+        .filter(|entry| normalize_path(entry) != candidate);
+    if let Some(file) = main.and_then(|entry| probe_file(fs, &normalize_path(&entry), options)) {
+        return Some(file);
+    }
+
+    options
+        .main_files
+        .iter()
+        .find_map(|name| probe_file(fs, &candidate.join(name), options))
+}
+
+/// Probe a single file path: aliased extensions first, then the exact path, so
+/// `.vue` or `.json` files resolve too, then each configured extension appended to it
+fn probe_file<F: FileSystem>(fs: &F, path: &Path, options: &ResolveOptions) -> Option<PathBuf> {
+    let file_name = path.file_name()?.to_string_lossy();
+    if let Some((stem, aliases)) = options.aliases_for(&file_name) {
+        // An aliased extension is only ever replaced, like webpack's `extensionAlias`
+        return aliases.iter().find_map(|ext| {
+            let file = path.with_file_name(format!("{stem}{ext}"));
+            fs.is_file(&file).then_some(file)
+        });
+    }
+
+    if fs.is_file(path) {
+        return Some(path.to_path_buf());
+    }
+    options.extensions.iter().find_map(|ext| {
+        let mut name = path.as_os_str().to_os_string();
+        name.push(ext);
+        let file = PathBuf::from(name);
+        fs.is_file(&file).then_some(file)
+    })
+}
@@ -0,0 +1,139 @@
+use super::super::file_system::FileSystem;
+use super::paths::{is_relative_specifier, relative_specifier};
+use super::{ResolvedExport, Resolver, SourceStyle};
+use crate::types::ExportInfo;
+use std::path::{Path, PathBuf};
+
+impl<F: FileSystem> Resolver<F> {
+    /// Write the source that points `importer` at `file` in the given style
+    /// Shortened forms are only used when they resolve back to the same file
+    #[must_use]
+    pub fn source_specifier(&self, importer: &Path, file: &Path, style: SourceStyle) -> String {
+        let dir = importer.parent().unwrap_or_else(|| Path::new(""));
+        let full = relative_specifier(dir, file);
+        let (main_dir, stem) = self.stems(file);
+        let relative = |forms: Vec<PathBuf>| -> Vec<String> {
+            forms
+                .iter()
+                .map(|form| relative_specifier(dir, form))
+                .collect()
+        };
+
+        let candidates: Vec<String> = match style {
+            SourceStyle::Absolute => return file.to_string_lossy().into_owned(),
+            SourceStyle::Extension => return full,
+            SourceStyle::FullySpecified => return runtime_specifier(&full),
+            SourceStyle::Extensionless => relative(stem.into_iter().collect()),
+            SourceStyle::Relative => relative(main_dir.into_iter().chain(stem).collect()),
+            SourceStyle::Alias => {
+                let forms: Vec<PathBuf> = main_dir.into_iter().chain(stem).collect();
+                let aliases = self.tsconfig.iter().flat_map(|tsconfig| {
+                    forms
+                        .iter()
+                        .map(PathBuf::as_path)
+                        .chain([file])
+                        .flat_map(|form| tsconfig.aliases_for(form))
+                        .collect::<Vec<_>>()
+                });
+                aliases.chain(relative(forms.clone())).collect()
+            }
+        };
+
+        let target = self.canonical(file);
+        candidates
+            .into_iter()
+            .find(|candidate| {
+                self.resolve_source(importer, candidate)
+                    .is_some_and(|resolved| self.canonical(&resolved) == target)
+            })
+            .unwrap_or(full)
+    }
+
+    /// Point every resolved export at its leaf module in the given style
+    /// Exports whose source did not resolve keep the source they were written with
+    #[must_use]
+    pub fn rewrite_sources(
+        &self,
+        importer: &Path,
+        resolved: Vec<ResolvedExport>,
+        style: SourceStyle,
+    ) -> Vec<ResolvedExport> {
+        resolved
+            .into_iter()
+            .map(|mut entry| {
+                if let Some(file) = &entry.file {
+                    entry.export.source = self.source_specifier(importer, file, style);
+                }
+                entry
+            })
+            .collect()
+    }
+
+    /// Give every relative source that resolves its runtime extension
+    /// Bare and aliased specifiers are left alone, as tsc does
+    #[must_use]
+    pub fn specify_sources(&self, importer: &Path, exports: Vec<ExportInfo>) -> Vec<ExportInfo> {
+        exports
+            .into_iter()
+            .map(|mut exp| {
+                if is_relative_specifier(&exp.source) {
+                    if let Some(file) = self.resolve_source(importer, &exp.source) {
+                        exp.source =
+                            self.source_specifier(importer, &file, SourceStyle::FullySpecified);
+                    }
+                }
+                exp
+            })
+            .collect()
+    }
+
+    /// Extensionless forms of `file`: its directory when it is a main file, and
+    /// the path without its extension
+    fn stems(&self, file: &Path) -> (Option<PathBuf>, Option<PathBuf>) {
+        let name = file
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let Some(stem) = self
+            .options
+            .extensions
+            .iter()
+            .find_map(|ext| name.strip_suffix(ext.as_str()))
+            .filter(|stem| !stem.is_empty())
+        else {
+            return (None, None);
+        };
+        let dir = file.parent().unwrap_or_else(|| Path::new(""));
+        let main_dir = self
+            .options
+            .main_files
+            .iter()
+            .any(|main| main == stem)
+            .then(|| dir.to_path_buf());
+        (main_dir, Some(dir.join(stem)))
+    }
+}
+
+/// TypeScript sources mapped to the file they compile to, as with tsc's
+/// `rewriteRelativeImportExtensions`; declaration files point at their module
+const RUNTIME_EXTENSIONS: &[(&str, &str)] = &[
+    (".d.ts", ".js"),
+    (".d.mts", ".mjs"),
+    (".d.cts", ".cjs"),
+    (".tsx", ".js"),
+    (".ts", ".js"),
+    (".mts", ".mjs"),
+    (".cts", ".cjs"),
+];
+
+/// Swap a TypeScript extension for the runtime one; other files are kept as they are
+fn runtime_specifier(specifier: &str) -> String {
+    RUNTIME_EXTENSIONS
+        .iter()
+        .find_map(|(source, runtime)| {
+            specifier
+                .strip_suffix(source)
+                .map(|stem| format!("{stem}{runtime}"))
+        })
+        .unwrap_or_else(|| specifier.to_string())
+}
@@ -0,0 +1,65 @@
+use super::super::file_system::FileSystem;
+use super::super::package_json::find_package_scope;
+use super::Resolver;
+use crate::types::ModuleSideEffects;
+use std::path::Path;
+
+impl<F: FileSystem> Resolver<F> {
+    /// Decide whether importing a module runs code beyond defining its exports
+    /// A `sideEffects` field in the owning `package.json` is trusted when present;
+    /// otherwise the module's top-level statements decide
+    pub fn side_effects(&self, path: &Path) -> ModuleSideEffects {
+        let key = self.canonical(path);
+        if let Some(verdict) = self.side_effects.borrow().get(&key) {
+            return verdict.clone();
+        }
+        let verdict = self.decide_side_effects(&key);
+        self.side_effects.borrow_mut().insert(key, verdict.clone());
+        verdict
+    }
+
+    /// Whether skipping the module when importing past it could drop code it runs
+    pub fn has_side_effects(&self, path: &Path) -> bool {
+        !self.side_effects(path).pure
+    }
+
+    /// Whether flattening may point past the module
+    pub(super) fn can_skip(&self, path: &Path) -> bool {
+        !self.side_effect_barriers || !self.has_side_effects(path)
+    }
+
+    fn decide_side_effects(&self, path: &Path) -> ModuleSideEffects {
+        let file = path.to_string_lossy().into_owned();
+        let package = path
+            .parent()
+            .and_then(|dir| find_package_scope(&self.fs, dir))
+            .and_then(|dir| self.load_package(&dir));
+        if let Some(package) = package {
+            if let Some(flags) = &package.side_effects {
+                let relative = path.strip_prefix(&package.dir).unwrap_or(path);
+                return ModuleSideEffects {
+                    file,
+                    pure: !flags.matches(relative),
+                    reason: "package.json".to_string(),
+                    lines: Vec::new(),
+                };
+            }
+        }
+
+        match self.load(path) {
+            Ok(module) => ModuleSideEffects {
+                file,
+                pure: module.side_effects.is_empty(),
+                reason: "analysis".to_string(),
+                lines: module.side_effects.clone(),
+            },
+            // Unreadable modules are assumed to have side effects
+            Err(_) => ModuleSideEffects {
+                file,
+                pure: false,
+                reason: "unreadable".to_string(),
+                lines: Vec::new(),
+            },
+        }
+    }
+}
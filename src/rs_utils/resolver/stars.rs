@@ -0,0 +1,139 @@
+use super::super::file_system::FileSystem;
+use super::super::specifier::exported_name;
+use super::chain::Chain;
+use super::flatten::leaf;
+use super::{ResolvedExport, Resolver};
+use crate::types::ExportInfo;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+/// A module that provides a name to one of a module's export statements
+struct Provider {
+    /// Index of the statement
+    group: usize,
+    /// Module declaring the binding
+    origin: PathBuf,
+    /// Provided through a star export that was not flattened
+    star: bool,
+}
+
+/// Names a module's statements export, gathered to apply `export *` semantics
+#[derive(Default)]
+struct Providers {
+    explicit: HashSet<String>,
+    names: HashMap<String, Vec<Provider>>,
+}
+
+impl<F: FileSystem> Resolver<F> {
+    /// Apply `export *` semantics to the resolved statements of one module, given as
+    /// one group of entries per statement of `exports`
+    /// A flattened star that shares a name with a star left in place, from a
+    /// different module, is put back as a star so the ambiguity stays; names
+    /// flattened out of stars are then dropped when an explicit export shadows
+    /// them or two stars provide them from different modules, and kept once otherwise
+    pub(super) fn settle_stars(
+        &self,
+        importer: &Path,
+        exports: &[ExportInfo],
+        mut groups: Vec<Vec<ResolvedExport>>,
+    ) -> Vec<ResolvedExport> {
+        if !groups.iter().flatten().any(|entry| entry.from_star) {
+            return groups.into_iter().flatten().collect();
+        }
+        let providers = loop {
+            let providers = self.providers(&groups);
+            let Some(group) = providers.star_collision() else {
+                break providers;
+            };
+            let exp = &exports[group];
+            groups[group] = self
+                .resolve_source(importer, &exp.source)
+                .map(|target| leaf(exp, exp.specifier.clone(), target))
+                .into_iter()
+                .collect();
+        };
+
+        let mut emitted: HashSet<String> = HashSet::new();
+        groups
+            .into_iter()
+            .flatten()
+            .filter(|entry| {
+                let Some(name) = exported_name(&entry.export) else {
+                    return true;
+                };
+                !entry.from_star
+                    || (!providers.explicit.contains(name)
+                        && !providers.is_ambiguous(name)
+                        && emitted.insert(name.to_string()))
+            })
+            .collect()
+    }
+
+    fn providers(&self, groups: &[Vec<ResolvedExport>]) -> Providers {
+        let mut providers = Providers::default();
+        for (group, entry) in groups
+            .iter()
+            .enumerate()
+            .flat_map(|(group, entries)| entries.iter().map(move |entry| (group, entry)))
+        {
+            match (exported_name(&entry.export), &entry.file) {
+                (Some(name), _) if !entry.from_star => {
+                    providers.explicit.insert(name.to_string());
+                }
+                (Some(name), file) => {
+                    let origin = file
+                        .clone()
+                        .unwrap_or_else(|| entry.export.source.clone().into());
+                    providers.add(name, Provider { group, origin, star: false });
+                }
+                // Stars left in place take part through their analyzed names
+                (None, Some(file)) => {
+                    let names = self
+                        .star_names(file, &mut Chain::default())
+                        .unwrap_or_default();
+                    for star in names {
+                        providers
+                            .add(&star.name, Provider { group, origin: star.origin, star: true });
+                    }
+                }
+                (None, None) => {}
+            }
+        }
+        providers
+    }
+}
+
+impl Providers {
+    fn add(&mut self, name: &str, provider: Provider) {
+        self.names
+            .entry(name.to_string())
+            .or_default()
+            .push(provider);
+    }
+
+    /// The first statement whose flattened names collide with a star left in place
+    fn star_collision(&self) -> Option<usize> {
+        self.names
+            .iter()
+            .filter(|(name, _)| !self.explicit.contains(*name))
+            .filter_map(|(_, providers)| {
+                providers.iter().filter(|p| !p.star).find_map(|flat| {
+                    providers
+                        .iter()
+                        .any(|p| p.star && p.group != flat.group && p.origin != flat.origin)
+                        .then_some(flat.group)
+                })
+            })
+            .min()
+    }
+
+    fn is_ambiguous(&self, name: &str) -> bool {
+        self.names.get(name).is_some_and(|providers| {
+            providers.iter().any(|p| {
+                providers
+                    .first()
+                    .is_some_and(|first| first.origin != p.origin)
+            })
+        })
+    }
+}
@@ -0,0 +1,66 @@
+use std::cmp::Ordering;
+
+/// Compare two strings treating digit runs as numbers
+/// `item2` sorts before `item10`; other characters compare byte-wise
+#[must_use]
+pub fn natural_cmp(a: &str, b: &str) -> Ordering {
+    let mut left = a.as_bytes();
+    let mut right = b.as_bytes();
+
+    loop {
+        match (left.first(), right.first()) {
+            (None, None) => return Ordering::Equal,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(x), Some(y)) if x.is_ascii_digit() && y.is_ascii_digit() => {
+                let (l_num, l_rest) = split_digits(left);
+                let (r_num, r_rest) = split_digits(right);
+                let ordering = compare_numbers(l_num, r_num);
+                if ordering != Ordering::Equal {
+                    return ordering;
+                }
+                left = l_rest;
+                right = r_rest;
+            }
+            (Some(x), Some(y)) => {
+                if x != y {
+                    return x.cmp(y);
+                }
+                left = &left[1..];
+                right = &right[1..];
+            }
+        }
+    }
+}
+
+/// Compare two strings ignoring ASCII case, falling back to byte order for ties
+#[must_use]
+pub fn case_insensitive_cmp(a: &str, b: &str) -> Ordering {
+    a.to_ascii_lowercase()
+        .cmp(&b.to_ascii_lowercase())
+        .then_with(|| a.cmp(b))
+}
+
+/// Split a leading run of ASCII digits from the rest of the input
+fn split_digits(input: &[u8]) -> (&[u8], &[u8]) {
+    let end = input
+        .iter()
+        .position(|c| !c.is_ascii_digit())
+        .unwrap_or(input.len());
+    input.split_at(end)
+}
+
+/// Compare two digit runs by numeric value without parsing them
+fn compare_numbers(a: &[u8], b: &[u8]) -> Ordering {
+    let a = trim_zeros(a);
+    let b = trim_zeros(b);
+    a.len().cmp(&b.len()).then_with(|| a.cmp(b))
+}
+
+fn trim_zeros(digits: &[u8]) -> &[u8] {
+    let start = digits
+        .iter()
+        .position(|c| *c != b'0')
+        .unwrap_or(digits.len());
+    &digits[start..]
+}
@@ -0,0 +1,66 @@
+mod compare;
+mod strategy;
+
+pub use strategy::SortStrategy;
+
+use crate::types::ExportInfo;
+use std::collections::HashMap;
+
+/// Sort exports first by source, then by specifier
+/// This groups exports from the same source together
+/// and alphabetically sorts specifiers within each group
+#[must_use]
+pub fn sort_exports(exports: Vec<ExportInfo>) -> Vec<ExportInfo> {
+    sort_exports_by(exports, SortStrategy::Alphabetical, SortStrategy::Alphabetical)
+}
+
+/// Sort export statements and the specifiers inside them independently
+/// Statements are ordered with `statements`; specifiers that end up in the same
+/// statement are ordered with `specifiers`
+#[must_use]
+pub fn sort_exports_by(
+    exports: Vec<ExportInfo>,
+    statements: SortStrategy,
+    specifiers: SortStrategy,
+) -> Vec<ExportInfo> {
+    let ranks: Vec<usize> = statement_ranks(&exports, statements);
+
+    let mut ranked: Vec<(usize, ExportInfo)> = ranks.into_iter().zip(exports).collect();
+    ranked.sort_by(|(a_rank, a), (b_rank, b)| {
+        a_rank
+            .cmp(b_rank)
+            .then_with(|| specifiers.compare_names(&a.specifier, &b.specifier))
+    });
+
+    ranked.into_iter().map(|(_, exp)| exp).collect()
+}
+
+/// Position of each export's statement once statements are sorted
+fn statement_ranks(exports: &[ExportInfo], statements: SortStrategy) -> Vec<usize> {
+    // One representative per statement, in first-appearance order
+    let mut indices: HashMap<(&str, &str, bool), usize> = HashMap::new();
+    let mut representatives: Vec<&ExportInfo> = Vec::new();
+    let statement_of: Vec<usize> = exports
+        .iter()
+        .map(|exp| {
+            *indices.entry(statement_key(exp)).or_insert_with(|| {
+                representatives.push(exp);
+                representatives.len() - 1
+            })
+        })
+        .collect();
+
+    let mut order: Vec<usize> = (0..representatives.len()).collect();
+    order.sort_by(|&a, &b| statements.compare_statements(representatives[a], representatives[b]));
+    let mut rank = vec![0; order.len()];
+    for (position, &index) in order.iter().enumerate() {
+        rank[index] = position;
+    }
+
+    statement_of.into_iter().map(|index| rank[index]).collect()
+}
+
+/// Exports sharing this key are emitted by the same statement
+const fn statement_key(exp: &ExportInfo) -> (&str, &str, bool) {
+    (exp.source.as_str(), exp.export_type.as_str(), exp.is_type_export)
+}
@@ -0,0 +1,87 @@
+use super::compare::{case_insensitive_cmp, natural_cmp};
+use crate::types::ExportInfo;
+use std::cmp::Ordering;
+
+/// How exports are ordered
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SortStrategy {
+    /// Byte-wise comparison
+    #[default]
+    Alphabetical,
+    /// Digit runs compare by value, so `./item2` sorts before `./item10`
+    Natural,
+    /// ASCII case is ignored, so `a` and `Z` sort as in a dictionary
+    CaseInsensitive,
+    /// Keep the original order
+    Preserve,
+    /// Package imports first, then relative paths, each alphabetical
+    ExternalsFirst,
+    /// Namespace, then default, then named, then type exports
+    Kind,
+}
+
+impl SortStrategy {
+    /// Parse a strategy from its option value
+    pub fn parse(value: &str) -> Result<Self, String> {
+        match value {
+            "alphabetical" => Ok(Self::Alphabetical),
+            "natural" => Ok(Self::Natural),
+            "case-insensitive" => Ok(Self::CaseInsensitive),
+            "preserve" => Ok(Self::Preserve),
+            "externals-first" => Ok(Self::ExternalsFirst),
+            "kind" => Ok(Self::Kind),
+            other => Err(format!(
+                "Unknown sort strategy \"{other}\" (expected alphabetical, natural, case-insensitive, preserve, externals-first or kind)"
+            )),
+        }
+    }
+
+    /// Parse an optional strategy, defaulting to alphabetical order
+    pub fn from_option(value: Option<&str>) -> Result<Self, String> {
+        value
+            .map(Self::parse)
+            .transpose()
+            .map(Option::unwrap_or_default)
+    }
+
+    /// Compare two plain names
+    /// Strategies that only make sense for statements compare alphabetically
+    #[must_use]
+    pub fn compare_names(self, a: &str, b: &str) -> Ordering {
+        match self {
+            Self::Natural => natural_cmp(a, b),
+            Self::CaseInsensitive => case_insensitive_cmp(a, b),
+            Self::Preserve => Ordering::Equal,
+            Self::Alphabetical | Self::ExternalsFirst | Self::Kind => a.cmp(b),
+        }
+    }
+
+    /// Compare the statements two exports belong to
+    #[must_use]
+    pub fn compare_statements(self, a: &ExportInfo, b: &ExportInfo) -> Ordering {
+        match self {
+            Self::ExternalsFirst => is_relative(&a.source)
+                .cmp(&is_relative(&b.source))
+                .then_with(|| a.source.cmp(&b.source)),
+            Self::Kind => kind_rank(a)
+                .cmp(&kind_rank(b))
+                .then_with(|| a.source.cmp(&b.source)),
+            other => other.compare_names(&a.source, &b.source),
+        }
+    }
+}
+
+fn is_relative(source: &str) -> bool {
+    source.starts_with('.') || source.starts_with('/')
+}
+
+fn kind_rank(exp: &ExportInfo) -> u8 {
+    if exp.is_type_export {
+        return 3;
+    }
+    match exp.export_type.as_str() {
+        "namespace" => 0,
+        "default" => 1,
+        _ => 2,
+    }
+}
@@ -0,0 +1,32 @@
+use crate::types::ExportInfo;
+
+/// Split a specifier such as `Button as Btn` into its local and exported names
+/// A plain specifier is both its own local and exported name
+#[must_use]
+pub fn split_specifier(specifier: &str) -> (&str, &str) {
+    match specifier.split_once(" as ") {
+        Some((local, exported)) => (local.trim(), exported.trim()),
+        None => (specifier.trim(), specifier.trim()),
+    }
+}
+
+/// Build a specifier from a local and an exported name
+#[must_use]
+pub fn join_specifier(local: &str, exported: &str) -> String {
+    if local == exported {
+        local.to_string()
+    } else {
+        format!("{local} as {exported}")
+    }
+}
+
+/// Name under which an export is visible to importers
+/// Returns `None` for `export * from` since its names are only known after resolution
+#[must_use]
+pub fn exported_name(exp: &ExportInfo) -> Option<&str> {
+    match exp.export_type.as_str() {
+        "namespace" if exp.specifier == "*" => None,
+        "named" => Some(split_specifier(&exp.specifier).1),
+        _ => Some(exp.specifier.as_str()),
+    }
+}
@@ -0,0 +1,91 @@
+/// Convert JSONC (JSON with comments and trailing commas) into plain JSON
+/// String contents are copied verbatim, so `//` inside a path is kept
+#[must_use]
+pub fn strip_jsonc(input: &str) -> String {
+    let without_comments = strip_comments(input);
+    strip_trailing_commas(&without_comments)
+}
+
+fn strip_comments(input: &str) -> String {
+    let mut output = String::with_capacity(input.len());
+    let mut chars = input.chars().peekable();
+    let mut in_string = false;
+
+    while let Some(c) = chars.next() {
+        if in_string {
+            output.push(c);
+            if c == '\\' {
+                if let Some(escaped) = chars.next() {
+                    output.push(escaped);
+                }
+            } else if c == '"' {
+                in_string = false;
+            }
+            continue;
+        }
+
+        match (c, chars.peek()) {
+            ('"', _) => {
+                in_string = true;
+                output.push(c);
+            }
+            ('/', Some('/')) => {
+                for next in chars.by_ref() {
+                    if next == '\n' {
+                        output.push('\n');
+                        break;
+                    }
+                }
+            }
+            ('/', Some('*')) => {
+                chars.next();
+                let mut previous = ' ';
+                for next in chars.by_ref() {
+                    if previous == '*' && next == '/' {
+                        break;
+                    }
+                    previous = next;
+                }
+            }
+            _ => output.push(c),
+        }
+    }
+
+    output
+}
+
+fn strip_trailing_commas(input: &str) -> String {
+    let chars: Vec<char> = input.chars().collect();
+    let mut output = String::with_capacity(input.len());
+    let mut in_string = false;
+    let mut index = 0;
+
+    while index < chars.len() {
+        let c = chars[index];
+        if in_string {
+            if c == '\\' {
+                output.push(c);
+                index += 1;
+                if let Some(&escaped) = chars.get(index) {
+                    output.push(escaped);
+                }
+            } else {
+                in_string = c != '"';
+                output.push(c);
+            }
+        } else if c == '"' {
+            in_string = true;
+            output.push(c);
+        } else if c == ',' {
+            let next = chars[index + 1..].iter().find(|n| !n.is_whitespace());
+            if !matches!(next, Some('}' | ']')) {
+                output.push(c);
+            }
+        } else {
+            output.push(c);
+        }
+        index += 1;
+    }
+
+    output
+}
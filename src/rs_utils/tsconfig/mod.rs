@@ -0,0 +1,136 @@
+mod jsonc;
+mod paths;
+
+pub use jsonc::strip_jsonc;
+
+use super::file_system::{read_file, FileSystem};
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+
+/// Maximum depth of `extends` chains, guarding against cycles
+const MAX_EXTENDS_DEPTH: usize = 32;
+
+/// Module resolution settings read from a tsconfig and the configs it extends
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TsConfig {
+    /// Absolute `compilerOptions.baseUrl`
+    pub base_url: Option<PathBuf>,
+    /// `compilerOptions.paths` patterns and their targets, in declaration order
+    pub paths: Vec<(String, Vec<String>)>,
+    /// Directory `paths` targets are relative to when no `baseUrl` is set
+    pub paths_dir: Option<PathBuf>,
+    /// `compilerOptions.moduleResolution`, or the mode implied by `module`
+    pub module_resolution: Option<String>,
+}
+
+impl TsConfig {
+    /// Load a tsconfig, following its `extends` chain
+    pub fn load<F: FileSystem>(fs: &F, path: &Path) -> Result<Self, String> {
+        let mut config = Self::default();
+        config.merge_file(fs, path, 0)?;
+        Ok(config)
+    }
+
+    /// Find the nearest `tsconfig.json` in `dir` or its ancestors
+    #[must_use]
+    pub fn find<F: FileSystem>(fs: &F, dir: &Path) -> Option<PathBuf> {
+        dir.ancestors()
+            .map(|ancestor| ancestor.join("tsconfig.json"))
+            .find(|candidate| fs.is_file(candidate))
+    }
+
+    /// Apply a config file on top of the configs it extends
+    fn merge_file<F: FileSystem>(
+        &mut self,
+        fs: &F,
+        path: &Path,
+        depth: usize,
+    ) -> Result<(), String> {
+        if depth > MAX_EXTENDS_DEPTH {
+            return Err(format!("tsconfig extends chain too deep at {}", path.display()));
+        }
+        let source = read_file(fs, path)?;
+        let json: Value = serde_json::from_str(&strip_jsonc(&source))
+            .map_err(|e| format!("Failed to parse {}: {e}", path.display()))?;
+        let dir = path.parent().unwrap_or_else(|| Path::new(""));
+
+        // Extended configs apply first so this file's settings win
+        let extends: Vec<&str> = match json.get("extends") {
+            Some(Value::String(parent)) => vec![parent.as_str()],
+            Some(Value::Array(parents)) => parents.iter().filter_map(Value::as_str).collect(),
+            _ => Vec::new(),
+        };
+        for parent in extends {
+            let parent_path = resolve_extends(fs, dir, parent).ok_or_else(|| {
+                format!("Cannot find tsconfig \"{parent}\" extended by {}", path.display())
+            })?;
+            self.merge_file(fs, &parent_path, depth + 1)?;
+        }
+
+        let Some(options) = json.get("compilerOptions") else {
+            return Ok(());
+        };
+        if let Some(mode) = module_resolution(options) {
+            self.module_resolution = Some(mode);
+        }
+        if let Some(base_url) = options.get("baseUrl").and_then(Value::as_str) {
+            self.base_url = Some(dir.join(base_url));
+        }
+        if let Some(Value::Object(paths)) = options.get("paths") {
+            self.paths = paths
+                .iter()
+                .map(|(pattern, targets)| {
+                    let targets = targets
+                        .as_array()
+                        .map(|t| {
+                            t.iter()
+                                .filter_map(Value::as_str)
+                                .map(str::to_string)
+                                .collect()
+                        })
+                        .unwrap_or_default();
+                    (pattern.clone(), targets)
+                })
+                .collect();
+            self.paths_dir = Some(dir.to_path_buf());
+        }
+        Ok(())
+    }
+}
+
+/// `moduleResolution`, falling back to the mode implied by `module`
+/// `node16`/`nodenext` imply the matching resolution and `preserve` implies `bundler`
+fn module_resolution(options: &Value) -> Option<String> {
+    if let Some(mode) = options.get("moduleResolution").and_then(Value::as_str) {
+        return Some(mode.to_string());
+    }
+    let module = options.get("module").and_then(Value::as_str)?;
+    match module.to_ascii_lowercase().as_str() {
+        "node16" | "nodenext" => Some(module.to_string()),
+        "preserve" => Some("bundler".to_string()),
+        _ => None,
+    }
+}
+
+/// Locate a config named in `extends`, either a path or a package in `node_modules`
+fn resolve_extends<F: FileSystem>(fs: &F, dir: &Path, specifier: &str) -> Option<PathBuf> {
+    let with_json = |path: PathBuf| -> Option<PathBuf> {
+        if fs.is_file(&path) {
+            return Some(path);
+        }
+        let mut name = path.clone().into_os_string();
+        name.push(".json");
+        let file = PathBuf::from(name);
+        if fs.is_file(&file) {
+            return Some(file);
+        }
+        let nested = path.join("tsconfig.json");
+        fs.is_file(&nested).then_some(nested)
+    };
+
+    if specifier.starts_with('.') || Path::new(specifier).is_absolute() {
+        return with_json(dir.join(specifier));
+    }
+    dir.ancestors()
+        .find_map(|ancestor| with_json(ancestor.join("node_modules").join(specifier)))
+}
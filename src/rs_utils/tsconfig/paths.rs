@@ -0,0 +1,100 @@
+use super::super::resolver::normalize_path;
+use super::TsConfig;
+use std::path::{Path, PathBuf};
+
+impl TsConfig {
+    /// Candidate paths for a non-relative specifier, in the order TypeScript tries them
+    /// The `paths` pattern with the longest prefix wins; `baseUrl` is tried last
+    #[must_use]
+    pub fn resolve_alias(&self, specifier: &str) -> Vec<PathBuf> {
+        let mut candidates: Vec<PathBuf> = Vec::new();
+
+        if let Some(base) = self.base_url.as_ref().or(self.paths_dir.as_ref()) {
+            if let Some((targets, captured)) = self.match_paths(specifier) {
+                candidates.extend(
+                    targets
+                        .iter()
+                        .map(|target| base.join(target.replacen('*', captured, 1))),
+                );
+            }
+        }
+
+        if let Some(base_url) = &self.base_url {
+            candidates.push(base_url.join(specifier));
+        }
+
+        candidates
+    }
+
+    /// Find the best matching `paths` pattern and the text matched by its `*`
+    fn match_paths<'a>(&self, specifier: &'a str) -> Option<(&[String], &'a str)> {
+        let mut best: Option<(&[String], &'a str, usize)> = None;
+
+        for (pattern, targets) in &self.paths {
+            let matched = match pattern.split_once('*') {
+                None => (pattern == specifier).then_some(("", usize::MAX)),
+                Some((prefix, suffix)) => specifier
+                    .strip_prefix(prefix)
+                    .and_then(|rest| rest.strip_suffix(suffix))
+                    .map(|captured| (captured, prefix.len())),
+            };
+            if let Some((captured, rank)) = matched {
+                if best.is_none_or(|(_, _, best_rank)| rank > best_rank) {
+                    best = Some((targets, captured, rank));
+                }
+            }
+        }
+
+        best.map(|(targets, captured, _)| (targets, captured))
+    }
+
+    /// Specifiers that `paths` or `baseUrl` map to `path`, in `paths` order
+    /// Wildcard targets capture whatever the `*` stands for in the path
+    #[must_use]
+    pub fn aliases_for(&self, path: &Path) -> Vec<String> {
+        let path = normalize_path(path).to_string_lossy().into_owned();
+        let mut aliases: Vec<String> = Vec::new();
+
+        if let Some(base) = self.base_url.as_ref().or(self.paths_dir.as_ref()) {
+            for (pattern, targets) in &self.paths {
+                for target in targets {
+                    if let Some(alias) = reverse_alias(base, pattern, target, &path) {
+                        aliases.push(alias);
+                    }
+                }
+            }
+        }
+
+        if let Some(base_url) = &self.base_url {
+            let base = normalize_path(base_url).to_string_lossy().into_owned();
+            if let Some(rest) = path.strip_prefix(&base).and_then(|r| r.strip_prefix('/')) {
+                aliases.push(rest.to_string());
+            }
+        }
+
+        aliases
+    }
+}
+
+/// The specifier a single `paths` entry maps to `path`, if any
+fn reverse_alias(base: &Path, pattern: &str, target: &str, path: &str) -> Option<String> {
+    let resolved = |part: &str| {
+        let mut joined = normalize_path(&base.join(part))
+            .to_string_lossy()
+            .into_owned();
+        if part.ends_with('/') {
+            joined.push('/');
+        }
+        joined
+    };
+    match (pattern.split_once('*'), target.split_once('*')) {
+        (Some((prefix, suffix)), Some((target_prefix, target_suffix))) => {
+            let captured = path
+                .strip_prefix(&resolved(target_prefix))?
+                .strip_suffix(target_suffix)?;
+            (!captured.is_empty()).then(|| format!("{prefix}{captured}{suffix}"))
+        }
+        (None, None) => (resolved(target) == path).then(|| pattern.to_string()),
+        _ => None,
+    }
+}
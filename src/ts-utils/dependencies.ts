@@ -0,0 +1,75 @@
+import type * as fs from 'node:fs';
+import path from 'node:path';
+import type { LoaderContext, ProcessResult } from '../barrel-loader.types';
+
+type Dependencies = Omit<ProcessResult, 'code'>;
+
+/**
+ * Wrap a filesystem so every file read, directory listed and path probed without
+ * success is recorded, mirroring the native `process_with_dependencies_napi`
+ */
+function trackDependencies(fileSystem: typeof fs): {
+  fileSystem: typeof fs;
+  dependencies: Dependencies;
+} {
+  const files = new Set<string>();
+  const contexts = new Set<string>();
+  const missing = new Set<string>();
+
+  function record<T>(filePath: unknown, found: Set<string> | null, read: () => T): T {
+    try {
+      const result = read();
+      if (result === undefined) missing.add(String(filePath));
+      else found?.add(String(filePath));
+      return result;
+    } catch (err) {
+      missing.add(String(filePath));
+      throw err;
+    }
+  }
+
+  const tracked = Object.create(fileSystem) as typeof fs;
+  Object.assign(tracked, {
+    readFileSync: (...args: Parameters<typeof fs.readFileSync>) =>
+      record(args[0], files, () => fileSystem.readFileSync(...args)),
+    statSync: (...args: Parameters<typeof fs.statSync>) => {
+      const stats = record(args[0], null, () => fileSystem.statSync(...args));
+      if (stats?.isFile()) files.add(String(args[0]));
+      return stats;
+    },
+    realpathSync: (...args: Parameters<typeof fs.realpathSync>) =>
+      record(args[0], null, () => fileSystem.realpathSync(...args)),
+    readdirSync: (...args: Parameters<typeof fs.readdirSync>) =>
+      record(args[0], contexts, () => fileSystem.readdirSync(...args)),
+  });
+
+  const dependencies: Dependencies = {
+    get fileDependencies() {
+      return Array.from(files).sort();
+    },
+    get contextDependencies() {
+      // Directories where a probe missed before a file was found decide the result too
+      const found = new Set(Array.from(files, (file) => path.dirname(file)));
+      const probed = Array.from(missing, (file) => path.dirname(file)).filter((dir) =>
+        found.has(dir)
+      );
+      return Array.from(new Set([...contexts, ...probed])).sort();
+    },
+    get missingDependencies() {
+      return Array.from(missing).sort();
+    },
+  };
+  return { fileSystem: tracked, dependencies };
+}
+
+/**
+ * Register dependencies with webpack so watch mode rebuilds the barrel when a
+ * leaf module changes or a probed path appears
+ */
+function reportDependencies(context: LoaderContext<unknown>, dependencies: Dependencies): void {
+  for (const file of dependencies.fileDependencies) context.addDependency?.(file);
+  for (const dir of dependencies.contextDependencies) context.addContextDependency?.(dir);
+  for (const file of dependencies.missingDependencies) context.addMissingDependency?.(file);
+}
+
+export { reportDependencies, trackDependencies };
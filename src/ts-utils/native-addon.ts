@@ -13,10 +13,28 @@ try {
   nativeAddon = {
     parse_exports_napi: rawAddon.parseExportsNapi as NativeAddon['parse_exports_napi'],
     process_barrel_file: rawAddon.processBarrelFile as NativeAddon['process_barrel_file'],
+    process_with_dependencies_napi:
+      rawAddon.processWithDependenciesNapi as NativeAddon['process_with_dependencies_napi'],
     remove_duplicates: rawAddon.removeDuplicates as NativeAddon['remove_duplicates'],
     sort_exports_napi: rawAddon.sortExportsNapi as NativeAddon['sort_exports_napi'],
     reconstruct_source_napi:
       rawAddon.reconstructSourceNapi as NativeAddon['reconstruct_source_napi'],
+    sort_exports_by_strategy_napi:
+      rawAddon.sortExportsByStrategyNapi as NativeAddon['sort_exports_by_strategy_napi'],
+    module_cache_stats_napi:
+      rawAddon.moduleCacheStatsNapi as NativeAddon['module_cache_stats_napi'],
+    resolve_barrel_napi: rawAddon.resolveBarrelNapi as NativeAddon['resolve_barrel_napi'],
+    resolve_source_napi: rawAddon.resolveSourceNapi as NativeAddon['resolve_source_napi'],
+    source_specifier_napi:
+      rawAddon.sourceSpecifierNapi as NativeAddon['source_specifier_napi'],
+    resolve_sources_napi: rawAddon.resolveSourcesNapi as NativeAddon['resolve_sources_napi'],
+    rebase_sources_napi: rawAddon.rebaseSourcesNapi as NativeAddon['rebase_sources_napi'],
+    rewrite_imports_napi: rawAddon.rewriteImportsNapi as NativeAddon['rewrite_imports_napi'],
+    detect_conflicts_napi: rawAddon.detectConflictsNapi as NativeAddon['detect_conflicts_napi'],
+    detect_cycles_napi: rawAddon.detectCyclesNapi as NativeAddon['detect_cycles_napi'],
+    analyze_side_effects_napi:
+      rawAddon.analyzeSideEffectsNapi as NativeAddon['analyze_side_effects_napi'],
+    ExportGraph: rawAddon.ExportGraph as NativeAddon['ExportGraph'],
   };
 } catch (err) {
   const error = err as Error;
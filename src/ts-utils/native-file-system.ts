@@ -0,0 +1,45 @@
+import type * as fs from 'node:fs';
+import type { NativeFileSystem } from '../barrel-loader.types';
+
+const adapters = new WeakMap<typeof fs, NativeFileSystem>();
+
+function orNull<T>(read: () => T): T | null {
+  try {
+    return read();
+  } catch {
+    return null;
+  }
+}
+
+/**
+ * Wrap a Node-style filesystem (webpack's `inputFileSystem`, memfs, `node:fs`) in the
+ * adapter the native resolver reads through. Missing paths return `null` instead of
+ * throwing, so no JavaScript exception crosses into Rust.
+ */
+function createNativeFileSystem(fileSystem: typeof fs): NativeFileSystem {
+  const cached = adapters.get(fileSystem);
+  if (cached) return cached;
+
+  const adapter: NativeFileSystem = {
+    readFile: (filePath) => orNull(() => fileSystem.readFileSync(filePath, 'utf-8').toString()),
+    stat: (filePath) =>
+      orNull(() => {
+        const stats = fileSystem.statSync(filePath);
+        return {
+          isFile: stats.isFile(),
+          isDirectory: stats.isDirectory(),
+          mtimeMs: stats.mtimeMs,
+        };
+      }),
+    realpath: (filePath) =>
+      orNull(() =>
+        fileSystem.realpathSync ? fileSystem.realpathSync(filePath).toString() : filePath
+      ),
+    readDir: (filePath) =>
+      orNull(() => fileSystem.readdirSync(filePath).map((entry) => entry.toString())),
+  };
+  adapters.set(fileSystem, adapter);
+  return adapter;
+}
+
+export { createNativeFileSystem };
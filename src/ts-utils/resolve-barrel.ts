@@ -2,11 +2,25 @@ import type * as fs from 'node:fs';
 import * as path from 'node:path';
 import type { BarrelLoaderOptions, ExportInfo } from '../barrel-loader.types';
 import { parseExports } from './parse';
-import { processExportEntry } from './resolve-utils';
+import { processExportEntry, resolveSourceFiles } from './resolve-utils';
+
+/**
+ * Follow symlinks when the filesystem can; unresolvable paths are kept as-is
+ */
+function realPath(filePath: string, fileSystem: typeof fs): string {
+  try {
+    return fileSystem.realpathSync ? fileSystem.realpathSync(filePath).toString() : filePath;
+  } catch {
+    return filePath;
+  }
+}
 
 /**
  * Resolve barrel files recursively to include all re-exported exports
- * Prevents infinite loops with visited tracking
+ * `visited` holds the files on the current path only, so a cycle stops at the file
+ * that closes it while a module reached through two branches (a diamond) is
+ * resolved on both. Use `detect_cycles_napi` to list the cycles themselves.
+ * Files are keyed by real path so symlinked copies (pnpm) are one module.
  */
 function resolveBarrelExportsRecursive(
   filePath: string,
@@ -14,7 +28,7 @@ function resolveBarrelExportsRecursive(
   options: BarrelLoaderOptions = {},
   visited: Set<string> = new Set()
 ): ExportInfo[] {
-  const absolutePath = path.resolve(filePath);
+  const absolutePath = realPath(path.resolve(filePath), fileSystem);
   if (visited.has(absolutePath)) return [];
   visited.add(absolutePath);
 
@@ -37,11 +51,19 @@ function resolveBarrelExportsRecursive(
       });
     }
 
+    // One native call resolves every source of the barrel
+    const sourceFiles = resolveSourceFiles(
+      allExports.map((exp) => exp.source),
+      filePath,
+      options,
+      fileSystem
+    );
     const resolved = new Map<string, ExportInfo>();
-    for (const exp of allExports) {
+    for (const [index, exp] of allExports.entries()) {
       try {
         const results = processExportEntry(
           exp,
+          sourceFiles[index] ?? null,
           filePath,
           fileSystem,
           options,
@@ -80,6 +102,8 @@ function resolveBarrelExportsRecursive(
     return finalResults;
   } catch {
     return [];
+  } finally {
+    visited.delete(absolutePath);
   }
 }
 
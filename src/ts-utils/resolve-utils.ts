@@ -1,6 +1,8 @@
 import type * as fs from 'node:fs';
 import * as path from 'node:path';
 import type { BarrelLoaderOptions, ExportInfo } from '../barrel-loader.types';
+import { nativeAddon } from './native-addon';
+import { createNativeFileSystem } from './native-file-system';
 import { parseExports } from './parse';
 
 const TYPE_EXPORTS_LIST_RE = /^export\s+type\s+\{([^}]+)\}/;
@@ -84,45 +86,115 @@ function extractTypeExportNames(sourceContent: string): string[] {
   return Array.from(names);
 }
 
+const DEFAULT_EXTENSIONS = ['.ts', '.tsx', '.js', '.jsx', '.mjs', '.mts', '.cjs', '.cts'];
+
 /**
- * Resolve source file path trying common extensions
+ * Resolve a source by probing `extensions` and `mainFiles`, for when the native
+ * addon is not available. Aliases, packages and import maps are not followed.
  */
-function resolveSourceFile(
+function probeSourceFile(
   sourceImport: string,
-  sourceDir: string,
+  importer: string,
+  options: BarrelLoaderOptions,
   fileSystem: typeof fs
 ): string | null {
-  const extensions = ['.ts', '.tsx', '.js', '.jsx', '.mjs', '.mts', '.cjs'];
-  const sourceFile = path.resolve(sourceDir, sourceImport);
-
-  // Check if the path already has a valid extension
-  const ext = path.extname(sourceFile);
-  if (ext && extensions.includes(ext)) {
-    return fileSystem.existsSync(sourceFile) ? sourceFile : null;
-  }
+  if (!sourceImport.startsWith('.')) return null;
+  const extensions = options.extensions ?? DEFAULT_EXTENSIONS;
+  const sourceFile = path.resolve(path.dirname(importer), sourceImport);
+  const isFile = (file: string) => {
+    try {
+      return fileSystem.statSync(file).isFile();
+    } catch {
+      return false;
+    }
+  };
+
+  if (extensions.includes(path.extname(sourceFile)) && isFile(sourceFile)) return sourceFile;
+  const candidates = [
+    ...extensions.map((extension) => sourceFile + extension),
+    ...(options.mainFiles ?? ['index']).flatMap((main) =>
+      extensions.map((extension) => path.join(sourceFile, main + extension))
+    ),
+  ];
+  return candidates.find(isFile) ?? null;
+}
 
-  // Try as a file with various extensions
-  for (const extension of extensions) {
-    const candidate = sourceFile + extension;
-    if (fileSystem.existsSync(candidate)) return candidate;
+/**
+ * Resolve every re-export source of a barrel, natively in a single call when the
+ * addon is available, honoring the loader's resolution options (`extensions`,
+ * `mainFiles`, `conditionNames`, `mainFields`, `moduleResolution`) and reading
+ * through the loader's filesystem
+ */
+function resolveSourceFiles(
+  sourceImports: string[],
+  importer: string,
+  options: BarrelLoaderOptions,
+  fileSystem: typeof fs
+): (string | null)[] {
+  if (!nativeAddon?.resolve_sources_napi) {
+    return sourceImports.map((source) => probeSourceFile(source, importer, options, fileSystem));
   }
+  return nativeAddon.resolve_sources_napi(
+    importer,
+    sourceImports,
+    options,
+    createNativeFileSystem(fileSystem)
+  );
+}
 
-  // Try as a directory with index files
-  if (fileSystem.existsSync(sourceFile) && fileSystem.statSync(sourceFile).isDirectory()) {
-    for (const extension of extensions) {
-      const indexFile = path.join(sourceFile, `index${extension}`);
-      if (fileSystem.existsSync(indexFile)) return indexFile;
-    }
-  }
+/**
+ * Resolve a single re-export source, see `resolveSourceFiles`
+ */
+function resolveSourceFile(
+  sourceImport: string,
+  importer: string,
+  options: BarrelLoaderOptions,
+  fileSystem: typeof fs
+): string | null {
+  return resolveSourceFiles([sourceImport], importer, options, fileSystem)[0] ?? null;
+}
 
-  return null;
+/**
+ * Rewrite sources written in `from` so they point at the same modules from `importer`,
+ * in the loader's `sourceStyle`, with one native call. Unresolvable sources are left
+ * as they are; without the addon, sources become extensionless relative paths.
+ */
+function rebaseSources(
+  exports: ExportInfo[],
+  from: string,
+  importer: string,
+  options: BarrelLoaderOptions,
+  fileSystem: typeof fs
+): ExportInfo[] {
+  const sources = exports.map((exp) => exp.source);
+  const rebased = nativeAddon?.rebase_sources_napi
+    ? nativeAddon.rebase_sources_napi(
+        from,
+        importer,
+        sources,
+        options,
+        createNativeFileSystem(fileSystem)
+      )
+    : sources.map((source) => {
+        const file = probeSourceFile(source, from, options, fileSystem);
+        if (!file) return source;
+        const extensionless = file.slice(0, file.length - path.extname(file).length);
+        const relative = path
+          .relative(path.dirname(importer), extensionless)
+          .split(path.sep)
+          .join('/');
+        return relative.startsWith('.') ? relative : `./${relative}`;
+      });
+  return exports.map((exp, index) => ({ ...exp, source: rebased[index] ?? exp.source }));
 }
 
 /**
- * Process a single export entry from a barrel file
+ * Process a single export entry from a barrel file, given its source as
+ * resolved by `resolveSourceFiles`
  */
 function processExportEntry(
   exp: ExportInfo,
+  sourceFile: string | null,
   filePath: string,
   fileSystem: typeof fs,
   options: BarrelLoaderOptions,
@@ -137,7 +209,6 @@ function processExportEntry(
 ): ExportInfo[] {
   const verbose = process.env.BARREL_LOADER_DEBUG === 'true';
 
-  const sourceFile = resolveSourceFile(exp.source, path.dirname(filePath), fileSystem);
   if (!sourceFile) return [exp];
 
   try {
@@ -162,26 +233,9 @@ function processExportEntry(
         });
       }
 
-      // When convertNamespaceToNamed is true and this is a namespace export,
-      // map the resolved exports to use the original source path
-      if (
-        options.convertNamespaceToNamed &&
-        exp.export_type === 'namespace' &&
-        exp.specifier === '*'
-      ) {
-        if (verbose) {
-          console.log(
-            '[processExportEntry] Mapping resolved exports to original source:',
-            exp.source
-          );
-        }
-        return resolved.map((r) => ({
-          ...r,
-          source: exp.source,
-        }));
-      }
-
-      return resolved;
+      // Sources come back relative to the nested barrel; point them at the leaf
+      // modules from the barrel being processed instead
+      return rebaseSources(resolved, sourceFile, filePath, options, fileSystem);
     }
 
     // When convertNamespaceToNamed is enabled, expand namespace exports to individual named exports
@@ -278,4 +332,4 @@ function processExportEntry(
   return [exp];
 }
 
-export { resolveSourceFile, processExportEntry };
+export { resolveSourceFile, resolveSourceFiles, processExportEntry };
@@ -15,14 +15,139 @@ pub struct ExportInfo {
     pub line: u32,
 }
 
+/// An export name that is provided by more than one source
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ExportConflict {
+    pub name: String,
+    pub origins: Vec<ExportInfo>,
+}
+
+/// One re-export statement on a cycle: `file` re-exports `source` at `line`
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct CycleStep {
+    pub file: String,
+    pub line: u32,
+    pub source: String,
+}
+
+/// Re-export chain that leads back to a file it started from
+/// The last step's `source` resolves to the first step's `file`
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ExportCycle {
+    pub steps: Vec<CycleStep>,
+}
+
+/// `stat` result returned by the JavaScript filesystem adapter
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct FileStats {
+    pub is_file: bool,
+    pub is_directory: bool,
+    pub mtime_ms: Option<f64>,
+}
+
+/// Module cache activity since the process started, and what its directory holds
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CacheStats {
+    pub hits: u32,
+    pub misses: u32,
+    pub writes: u32,
+    pub evictions: u32,
+    pub entries: u32,
+    pub bytes: f64,
+}
+
+/// Transformed barrel and every path it was built from, for watch mode
+/// `missing_dependencies` lists paths that were probed and not found
+#[napi(object)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ProcessResult {
+    pub code: String,
+    pub file_dependencies: Vec<String>,
+    /// Directories where a probe missed before a file was found
+    pub context_dependencies: Vec<String>,
+    pub missing_dependencies: Vec<String>,
+    /// JSON export manifest, when `manifest` is enabled
+    pub manifest: Option<String>,
+}
+
+/// A barrel whose flattened exports changed, with the names that did
+/// `*` stands for star exports that are kept as they are
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct AffectedBarrel {
+    pub barrel: String,
+    pub names: Vec<String>,
+}
+
+/// Whether importing a module runs code, and what decided it
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ModuleSideEffects {
+    pub file: String,
+    pub pure: bool,
+    /// `package.json` when its `sideEffects` field decided, `analysis` when the
+    /// module's own statements did, or `unreadable`
+    pub reason: String,
+    /// Lines of the top-level statements found to have side effects
+    pub lines: Vec<u32>,
+}
+
 /// Options for the barrel loader
 #[napi(object)]
 #[derive(Debug, Clone, Default)]
 pub struct BarrelLoaderOptions {
+    /// Preset enabling resolution, namespace expansion, conflict handling,
+    /// dedup and sorting; options set explicitly take precedence
     pub optimize: Option<bool>,
     pub sort: Option<bool>,
     pub remove_duplicates: Option<bool>,
     pub verbose: Option<bool>,
+    /// Threads used to read and parse the barrel graph; defaults to the available cores
+    pub threads: Option<u32>,
+    /// Directory storing parse results between builds; caching is off when unset
+    pub cache_directory: Option<String>,
+    /// Size limit of the cache directory in megabytes; defaults to 256
+    pub cache_max_size: Option<u32>,
     pub convert_namespace_to_named: Option<bool>,
+    /// Names consumers import from the barrel (webpack `usedExports`); other
+    /// exports are dropped unless their module has side effects
+    pub used_exports: Option<Vec<String>>,
+    /// Keep type exports when pruning to `used_exports`; defaults to true
+    pub keep_type_exports: Option<bool>,
+    /// Also return a versioned JSON manifest tracing every export to its origin
+    pub manifest: Option<bool>,
     pub resolve_barrel_exports: Option<bool>,
+    /// tsconfig used for `paths`/`baseUrl` aliases; the nearest one is used when unset
+    pub tsconfig: Option<String>,
+    /// Follow bare specifiers into `node_modules` packages (e.g. workspace barrels)
+    pub resolve_packages: Option<bool>,
+    /// Import map files applied to re-export sources before file probing
+    pub import_maps: Option<Vec<String>>,
+    /// Extensions tried for extensionless sources, in order (webpack `resolve.extensions`)
+    pub extensions: Option<Vec<String>>,
+    /// File names tried inside directories (webpack `resolve.mainFiles`)
+    pub main_files: Option<Vec<String>>,
+    /// Conditions matched in package `exports`/`imports` (webpack `resolve.conditionNames`)
+    pub condition_names: Option<Vec<String>>,
+    /// Package fields used when `exports` is absent (webpack `resolve.mainFields`)
+    pub main_fields: Option<Vec<String>>,
+    /// "node10" | "node16" | "nodenext" | "bundler"; defaults to the tsconfig's setting
+    pub module_resolution: Option<String>,
+    /// Output real paths for symlinked modules (webpack `resolve.symlinks`); defaults to true
+    pub symlinks: Option<bool>,
+    /// How flattened sources are written: "relative" | "extensionless" | "extension"
+    /// | "alias" | "fully-specified" | "absolute"; defaults to "relative"
+    pub source_style: Option<String>,
+    /// "ignore" | "error" | "warn" | "first-wins" | "last-wins"
+    pub conflict_policy: Option<String>,
+    /// Statement order: "alphabetical" | "natural" | "case-insensitive" | "preserve"
+    /// | "externals-first" | "kind"
+    pub sort_strategy: Option<String>,
+    /// Specifier order inside a statement: "alphabetical" | "natural" | "case-insensitive" | "preserve"
+    pub specifier_sort_strategy: Option<String>,
 }
@@ -3,6 +3,9 @@
  * Run with: node test.cjs or pnpm test
  */
 
+const fs = require('node:fs');
+const os = require('node:os');
+const path = require('node:path');
 const addon = require('./native/barrel_loader_rs.node');
 
 let passed = 0;
@@ -150,6 +153,36 @@ export type { ButtonProps } from './ui/button.tsx';
   assert(result.split('\n').length > 0, 'Pipeline produces output');
 }
 
+function testLoaderDefaults() {
+  console.log('\n--- Testing loader defaults ---');
+
+  const loader = require('./dist/index.cjs');
+  const root = fs.mkdtempSync(path.join(os.tmpdir(), 'barrel-loader-'));
+  const files = {
+    'index.ts': "export { Alert } from './ui';\nexport { Button } from './button';\n",
+    'button.ts': 'export const Button = 1;\n',
+    'ui/index.ts': "export { Alert } from './alert';\n",
+    'ui/alert.ts': 'export const Alert = 2;\n',
+  };
+  for (const [file, content] of Object.entries(files)) {
+    fs.mkdirSync(path.dirname(path.join(root, file)), { recursive: true });
+    fs.writeFileSync(path.join(root, file), content);
+  }
+
+  try {
+    const output = loader.call(
+      { resourcePath: path.join(root, 'index.ts'), getOptions: () => ({}) },
+      files['index.ts']
+    );
+    assert(
+      output === 'export { Button } from "./button";\nexport { Alert } from "./ui/alert";\n',
+      'Loader flattens nested barrels and sorts by default'
+    );
+  } finally {
+    fs.rmSync(root, { recursive: true, force: true });
+  }
+}
+
 // Run all tests
 try {
   testParseExports();
@@ -157,6 +190,7 @@ try {
   testSortExports();
   testReconstructSource();
   testIntegration();
+  testLoaderDefaults();
 
   console.log('\n========================================');
   console.log(`Tests passed: ${passed}`);
@@ -168,6 +202,6 @@ try {
   }
 } catch (err) {
   console.error('\nTest execution failed:', err);
-  console.error('\nNote: Ensure native addon exists by running: pnpm build');
+  console.error('\nNote: Ensure the native addon and dist exist by running: pnpm build');
   process.exit(1);
 }
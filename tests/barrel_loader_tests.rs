@@ -1,3 +1,5 @@
+mod common;
+
 use barrel_loader::{BarrelLoader, BarrelLoaderOptions};
 
 #[test]
@@ -23,3 +25,38 @@ export { Button } from "./Button";"#;
     let count = result.matches("export").count();
     assert_eq!(count, 1);
 }
+
+#[test]
+fn test_process_resolves_barrel_exports() {
+    let fixture = common::Fixture::new(&[
+        ("index.ts", "export { Button } from \"./ui\";\n"),
+        ("ui/index.ts", "export { Button } from \"./button\";\n"),
+        ("ui/button.ts", "export const Button = 1;\n"),
+    ]);
+    let loader = BarrelLoader::new(BarrelLoaderOptions {
+        resolve_barrel_exports: Some(true),
+        ..Default::default()
+    });
+    let source = std::fs::read_to_string(fixture.path("index.ts")).unwrap();
+    let result = loader
+        .process(&source, &fixture.source("index.ts"))
+        .unwrap();
+    assert_eq!(result, "export { Button } from \"./ui/button\";\n");
+}
+
+#[test]
+fn test_process_groups_sources_by_resolved_module() {
+    let fixture = common::Fixture::new(&[
+        ("kit/package.json", r#"{ "main": "./src/main.ts" }"#),
+        ("kit/src/main.ts", "export const a = 1;\nexport const b = 2;\n"),
+    ]);
+    let loader = BarrelLoader::new(BarrelLoaderOptions {
+        resolve_barrel_exports: Some(true),
+        ..Default::default()
+    });
+    let source = r#"export { a } from "./kit/src/main.ts";
+export { b } from "./kit";
+export { a } from "./kit";"#;
+    let result = loader.process(source, &fixture.source("index.ts")).unwrap();
+    assert_eq!(result, "export { a, b } from \"./kit/src/main\";\n");
+}
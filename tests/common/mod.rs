@@ -0,0 +1,112 @@
+#![allow(dead_code)]
+
+use barrel_loader::{
+    BarrelLoader, BarrelLoaderOptions, FileSystem, MemoryFileSystem, OsFileSystem, ProcessResult,
+};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// Project files for a test, in a temporary directory that is removed when
+/// dropped, or under `/app` in a `MemoryFileSystem`
+pub struct Fixture {
+    root: PathBuf,
+    memory: Option<MemoryFileSystem>,
+}
+
+impl Fixture {
+    pub fn new(files: &[(&str, &str)]) -> Self {
+        let id = NEXT_ID.fetch_add(1, Ordering::SeqCst);
+        let root =
+            std::env::temp_dir().join(format!("barrel-loader-test-{}-{id}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        let fixture = Self { root, memory: None };
+        for (path, content) in files {
+            fixture.write(path, content);
+        }
+        fixture
+    }
+
+    /// Fixture kept in memory, for tests that read through a custom filesystem
+    pub fn in_memory(files: &[(&str, &str)]) -> Self {
+        let fixture = Self { root: PathBuf::from("/app"), memory: Some(MemoryFileSystem::new()) };
+        files
+            .iter()
+            .fold(fixture, |fixture, (path, content)| fixture.with_file(path, content))
+    }
+
+    /// Add a file, or replace one the fixture was created with
+    #[must_use]
+    pub fn with_file(mut self, path: &str, content: &str) -> Self {
+        let file = self.path(path);
+        if let Some(memory) = &mut self.memory {
+            memory.insert_file(file, content);
+        } else {
+            self.write(path, content);
+        }
+        self
+    }
+
+    /// Add a symlink at `link` pointing to `target`, relative to the link's directory
+    #[must_use]
+    pub fn with_symlink(mut self, link: &str, target: &str) -> Self {
+        let link = self.path(link);
+        if let Some(memory) = self.memory.take() {
+            self.memory = Some(memory.with_symlink(link, target));
+        } else {
+            fs::create_dir_all(link.parent().unwrap()).unwrap();
+            std::os::unix::fs::symlink(target, link).unwrap();
+        }
+        self
+    }
+
+    /// Filesystem of an in-memory fixture
+    pub const fn file_system(&self) -> &MemoryFileSystem {
+        self.memory.as_ref().expect("not an in-memory fixture")
+    }
+
+    /// Loader reading the fixture's files
+    pub fn loader(&self, options: BarrelLoaderOptions) -> BarrelLoader<&dyn FileSystem> {
+        let fs: &dyn FileSystem = match &self.memory {
+            Some(memory) => memory,
+            None => &OsFileSystem,
+        };
+        BarrelLoader::new(options).with_file_system(fs)
+    }
+
+    /// Process `source` as the contents of the fixture file `path`
+    pub fn process(&self, source: &str, path: &str, options: BarrelLoaderOptions) -> ProcessResult {
+        self.loader(options)
+            .process_with_dependencies(source, &self.source(path))
+            .unwrap()
+    }
+
+    pub fn write(&self, path: &str, content: &str) {
+        let file = self.root.join(path);
+        fs::create_dir_all(file.parent().unwrap()).unwrap();
+        fs::write(file, content).unwrap();
+    }
+
+    pub fn path(&self, path: &str) -> PathBuf {
+        self.root.join(path)
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Path of a fixture file as the string the resolver emits
+    pub fn source(&self, path: &str) -> String {
+        self.path(path).to_string_lossy().into_owned()
+    }
+}
+
+impl Drop for Fixture {
+    fn drop(&mut self) {
+        if self.memory.is_none() {
+            let _ = fs::remove_dir_all(&self.root);
+        }
+    }
+}
@@ -0,0 +1,88 @@
+mod common;
+
+use barrel_loader::{
+    BarrelLoader, BarrelLoaderOptions, ConflictPolicy, PipelineConfig, SortStrategy,
+};
+use common::Fixture;
+use rstest::rstest;
+
+fn config(options: &BarrelLoaderOptions) -> PipelineConfig {
+    PipelineConfig::from_options(options).unwrap()
+}
+
+#[test]
+fn test_defaults_leave_optional_stages_off() {
+    let config = config(&BarrelLoaderOptions::default());
+    assert!(!config.resolve_barrel_exports);
+    assert!(!config.convert_namespace_to_named);
+    assert!(config.remove_duplicates);
+    assert_eq!(config.conflict_policy, ConflictPolicy::Ignore);
+    assert!(!config.sort);
+}
+
+#[test]
+fn test_optimize_enables_full_pipeline() {
+    let config = config(&BarrelLoaderOptions { optimize: Some(true), ..Default::default() });
+    assert!(config.resolve_barrel_exports);
+    assert!(config.convert_namespace_to_named);
+    assert!(config.remove_duplicates);
+    assert_eq!(config.conflict_policy, ConflictPolicy::Warn);
+    assert!(config.sort);
+    assert_eq!(config.sort_strategy, SortStrategy::Alphabetical);
+}
+
+#[rstest]
+#[case(BarrelLoaderOptions { resolve_barrel_exports: Some(false), ..Default::default() }, "resolve")]
+#[case(BarrelLoaderOptions { convert_namespace_to_named: Some(false), ..Default::default() }, "convert")]
+#[case(BarrelLoaderOptions { remove_duplicates: Some(false), ..Default::default() }, "dedupe")]
+#[case(BarrelLoaderOptions { sort: Some(false), ..Default::default() }, "sort")]
+fn test_explicit_options_override_preset(
+    #[case] overrides: BarrelLoaderOptions,
+    #[case] disabled: &str,
+) {
+    let config = config(&BarrelLoaderOptions { optimize: Some(true), ..overrides });
+    let stages = [
+        ("resolve", config.resolve_barrel_exports),
+        ("convert", config.convert_namespace_to_named),
+        ("dedupe", config.remove_duplicates),
+        ("sort", config.sort),
+    ];
+    for (stage, enabled) in stages {
+        assert_eq!(enabled, stage != disabled, "stage {stage}");
+    }
+}
+
+#[test]
+fn test_preset_keeps_explicit_policy_and_strategy() {
+    let config = config(&BarrelLoaderOptions {
+        optimize: Some(true),
+        conflict_policy: Some("error".to_string()),
+        sort_strategy: Some("natural".to_string()),
+        ..Default::default()
+    });
+    assert_eq!(config.conflict_policy, ConflictPolicy::Error);
+    assert_eq!(config.sort_strategy, SortStrategy::Natural);
+}
+
+#[test]
+fn test_invalid_option_values_are_rejected() {
+    let options =
+        BarrelLoaderOptions { sort_strategy: Some("random".to_string()), ..Default::default() };
+    assert!(PipelineConfig::from_options(&options).is_err());
+}
+
+#[test]
+fn test_optimize_processes_barrel_end_to_end() {
+    let fixture = Fixture::new(&[
+        ("index.ts", "export * from \"./b\";\nexport * from \"./a\";\n"),
+        ("a.ts", "export const Shared = 1;\nexport const Alpha = 1;\n"),
+        ("b.ts", "export const Shared = 2;\nexport type Beta = string;\n"),
+    ]);
+    let loader =
+        BarrelLoader::new(BarrelLoaderOptions { optimize: Some(true), ..Default::default() });
+    let source = std::fs::read_to_string(fixture.path("index.ts")).unwrap();
+    let result = loader
+        .process(&source, &fixture.source("index.ts"))
+        .unwrap();
+    assert_eq!(result, "export { Alpha } from \"./a\";\nexport type { Beta } from \"./b\";\n");
+}
@@ -0,0 +1,132 @@
+mod common;
+
+use barrel_loader::{detect_conflicts_napi, BarrelLoader, BarrelLoaderOptions};
+use common::Fixture;
+use rstest::rstest;
+
+const SOURCE: &str = r#"export { Button } from "./a";
+export { Button } from "./b";
+export { Input } from "./a";"#;
+
+fn process(policy: &str) -> Result<String, String> {
+    let loader = BarrelLoader::new(BarrelLoaderOptions {
+        conflict_policy: Some(policy.to_string()),
+        ..Default::default()
+    });
+    loader.process(SOURCE, "/path/to/index.ts")
+}
+
+#[test]
+fn test_detect_conflicts_reports_both_origins() {
+    let exports = barrel_loader::parse_exports_napi(SOURCE.to_string()).unwrap();
+    let conflicts = detect_conflicts_napi(exports);
+    assert_eq!(conflicts.len(), 1);
+    assert_eq!(conflicts[0].name, "Button");
+    let sources: Vec<&str> = conflicts[0]
+        .origins
+        .iter()
+        .map(|o| o.source.as_str())
+        .collect();
+    assert_eq!(sources, vec!["./a", "./b"]);
+}
+
+#[test]
+fn test_aliased_export_conflicts_on_exported_name() {
+    let source = r#"export { Button } from "./a";
+export { Base as Button } from "./b";"#;
+    let exports = barrel_loader::parse_exports_napi(source.to_string()).unwrap();
+    assert_eq!(detect_conflicts_napi(exports).len(), 1);
+}
+
+#[rstest]
+#[case("warn", false, false)]
+#[case("first-wins", true, false)]
+#[case("last-wins", false, true)]
+fn test_conflict_policies(#[case] policy: &str, #[case] keeps_a: bool, #[case] keeps_b: bool) {
+    let result = process(policy).unwrap();
+    assert_eq!(result.contains(r#"export { Button, Input } from "./a";"#), keeps_a);
+    assert_eq!(result.contains(r#"export { Button } from "./b";"#), keeps_b);
+    assert!(result.contains("Input"));
+}
+
+#[test]
+fn test_warn_policy_dropping_every_export_removes_them_from_the_code() {
+    let loader = BarrelLoader::new(BarrelLoaderOptions {
+        conflict_policy: Some("warn".to_string()),
+        ..Default::default()
+    });
+    let source = "import \"./styles.css\";\nexport { Button } from \"./a\";\nexport { Button } from \"./b\";\n";
+    assert_eq!(
+        loader.process(source, "/path/to/index.ts").unwrap(),
+        "import \"./styles.css\";\nexport {};\n"
+    );
+}
+
+#[test]
+fn test_error_policy_fails_with_both_origins() {
+    let err = process("error").unwrap_err();
+    assert!(err.contains("./a (line 1)"));
+    assert!(err.contains("./b (line 2)"));
+}
+
+#[test]
+fn test_unknown_policy_is_rejected() {
+    assert!(process("random").is_err());
+}
+
+const STARS: &str = "export * from \"./a\";\nexport * from \"./b\";\n";
+
+fn process_stars(policy: &str, convert: bool) -> Result<String, String> {
+    let fixture = Fixture::in_memory(&[
+        ("src/a.ts", "export const Button = 1;\nexport const OnlyA = 1;\n"),
+        ("src/b.ts", "export const Button = 2;\n"),
+    ]);
+    fixture
+        .loader(BarrelLoaderOptions {
+            conflict_policy: Some(policy.to_string()),
+            convert_namespace_to_named: Some(convert),
+            ..Default::default()
+        })
+        .process(STARS, "/app/src/index.ts")
+}
+
+#[rstest]
+fn test_error_policy_reports_star_conflicts(#[values(false, true)] convert: bool) {
+    let err = process_stars("error", convert).unwrap_err();
+    assert!(err.contains("export \"Button\" is provided by ./a (line 1), ./b (line 2)"));
+}
+
+#[rstest]
+#[case(
+    false,
+    "export * from \"./a\";\nexport { Button } from \"./a\";\nexport * from \"./b\";\n"
+)]
+#[case(true, "export { OnlyA, Button } from \"./a\";\n")]
+fn test_first_wins_policy_resolves_star_conflicts(#[case] convert: bool, #[case] expected: &str) {
+    assert_eq!(process_stars("first-wins", convert).unwrap(), expected);
+}
+
+#[test]
+fn test_last_wins_policy_resolves_star_conflicts() {
+    assert_eq!(
+        process_stars("last-wins", true).unwrap(),
+        "export { OnlyA } from \"./a\";\nexport { Button } from \"./b\";\n"
+    );
+}
+
+#[test]
+fn test_star_conflicts_resolve_sources_through_tsconfig_paths() {
+    let fixture = Fixture::in_memory(&[
+        ("tsconfig.json", r#"{ "compilerOptions": { "paths": { "@/*": ["./src/*"] } } }"#),
+        ("src/a.ts", "export const Button = 1;\n"),
+        ("src/b.ts", "export const Button = 2;\n"),
+    ]);
+    let err = fixture
+        .loader(BarrelLoaderOptions {
+            conflict_policy: Some("error".to_string()),
+            ..Default::default()
+        })
+        .process("export * from \"@/a\";\nexport * from \"@/b\";\n", "/app/src/index.ts")
+        .unwrap_err();
+    assert!(err.contains("export \"Button\" is provided by @/a (line 1), @/b (line 2)"));
+}
@@ -0,0 +1,132 @@
+mod common;
+
+use barrel_loader::{
+    describe_cycle, BarrelLoader, BarrelLoaderOptions, CycleStep, ExportCycle, Resolver,
+};
+use common::Fixture;
+
+fn step(fixture: &Fixture, file: &str, line: u32, source: &str) -> CycleStep {
+    CycleStep { file: fixture.source(file), line, source: source.to_string() }
+}
+
+fn specifiers(resolver: &Resolver, fixture: &Fixture, barrel: &str) -> Vec<String> {
+    resolver
+        .resolve_barrel(&fixture.path(barrel))
+        .unwrap()
+        .into_iter()
+        .map(|r| r.export.specifier)
+        .collect()
+}
+
+#[test]
+fn test_star_cycle_reported_with_files_and_lines() {
+    let fixture = Fixture::new(&[
+        ("index.ts", "export * from \"./a\";\n"),
+        ("a.ts", "export { x } from \"./x\";\nexport * from \"./b\";\n"),
+        ("b.ts", "export * from \"./a\";\nexport { y } from \"./y\";\n"),
+        ("x.ts", "export const x = 1;\n"),
+        ("y.ts", "export const y = 1;\n"),
+    ]);
+    let resolver = Resolver::new();
+
+    assert_eq!(specifiers(&resolver, &fixture, "index.ts"), vec!["x", "y"]);
+    assert_eq!(
+        resolver.cycles(),
+        vec![ExportCycle {
+            steps: vec![
+                step(&fixture, "a.ts", 2, "./b"),
+                step(&fixture, "b.ts", 1, "./a"),
+            ],
+        }]
+    );
+}
+
+#[test]
+fn test_diamond_is_not_a_cycle() {
+    let fixture = Fixture::new(&[
+        ("index.ts", "export * from \"./a\";\nexport * from \"./b\";\n"),
+        ("a.ts", "export { shared } from \"./d\";\nexport { onlyA } from \"./d\";\n"),
+        ("b.ts", "export { shared } from \"./d\";\n"),
+        ("d.ts", "export const shared = 1;\nexport const onlyA = 2;\n"),
+    ]);
+    let resolver = Resolver::new();
+
+    assert_eq!(specifiers(&resolver, &fixture, "index.ts"), vec!["shared", "onlyA"]);
+    assert!(resolver.cycles().is_empty());
+}
+
+#[test]
+fn test_named_cycle_keeps_export_pointing_into_cycle() {
+    let fixture = Fixture::new(&[
+        ("index.ts", "export { foo } from \"./a\";\n"),
+        ("a.ts", "export { foo } from \"./b\";\n"),
+        ("b.ts", "export { foo } from \"./a\";\n"),
+    ]);
+    let resolver = Resolver::new();
+    let flattened = resolver.resolve_barrel(&fixture.path("index.ts")).unwrap();
+
+    assert_eq!(flattened.len(), 1);
+    assert_eq!(flattened[0].export.specifier, "foo");
+    assert_eq!(resolver.cycles().len(), 1);
+    assert_eq!(
+        describe_cycle(&resolver.cycles()[0]),
+        format!(
+            "{}:1 -> {}:1 -> {}",
+            fixture.source("a.ts"),
+            fixture.source("b.ts"),
+            fixture.source("a.ts")
+        )
+    );
+}
+
+#[test]
+fn test_self_reexport_is_a_one_step_cycle() {
+    let fixture =
+        Fixture::new(&[("index.ts", "export * from \"./index\";\nexport { a } from \"./a\";\n")]);
+    let resolver = Resolver::new();
+    let _ = resolver.resolve_barrel(&fixture.path("index.ts")).unwrap();
+
+    assert_eq!(
+        resolver.cycles(),
+        vec![ExportCycle { steps: vec![step(&fixture, "index.ts", 1, "./index")] }]
+    );
+}
+
+#[test]
+fn test_find_cycles_follows_stars_of_modules_with_declarations() {
+    let fixture = Fixture::new(&[
+        ("index.ts", "export * from \"./a\";\n"),
+        ("a.ts", "export const a = 1;\nexport * from \"./b\";\n"),
+        ("b.ts", "export const b = 1;\nexport * from \"./a\";\n"),
+    ]);
+    let cycles = BarrelLoader::new(BarrelLoaderOptions::default())
+        .find_cycles(&fixture.source("index.ts"))
+        .unwrap();
+
+    assert_eq!(
+        cycles,
+        vec![ExportCycle {
+            steps: vec![
+                step(&fixture, "a.ts", 2, "./b"),
+                step(&fixture, "b.ts", 2, "./a"),
+            ],
+        }]
+    );
+}
+
+#[test]
+fn test_cycle_with_namespace_conversion_still_expands() {
+    let fixture = Fixture::new(&[
+        ("index.ts", "export * from \"./a\";\n"),
+        ("a.ts", "export const a = 1;\nexport * from \"./b\";\n"),
+        ("b.ts", "export const b = 1;\nexport * from \"./a\";\n"),
+    ]);
+    let output = BarrelLoader::new(BarrelLoaderOptions {
+        convert_namespace_to_named: Some(true),
+        ..Default::default()
+    })
+    .process("export * from \"./a\";\n", &fixture.source("index.ts"))
+    .unwrap();
+
+    assert_eq!(output, "export { a, b } from \"./a\";\n");
+}
@@ -1,4 +1,7 @@
-use barrel_loader::{remove_duplicates, ExportInfo};
+mod common;
+
+use barrel_loader::{parse_exports_napi, remove_duplicates, BarrelLoaderOptions, ExportInfo};
+use common::Fixture;
 
 #[test]
 fn test_remove_duplicates() {
@@ -21,3 +24,83 @@ fn test_remove_duplicates() {
     let deduped = remove_duplicates(exports);
     assert_eq!(deduped.len(), 1);
 }
+
+#[test]
+fn test_type_export_merged_into_value_export() {
+    let source = r#"export type { User } from "./user";
+export { User } from "./user";
+export type { Role } from "./user";"#;
+    let exports = parse_exports_napi(source.to_string()).unwrap();
+    let deduped = remove_duplicates(exports);
+    assert_eq!(deduped.len(), 2);
+    assert!(deduped
+        .iter()
+        .any(|e| e.specifier == "User" && !e.is_type_export));
+    assert!(deduped
+        .iter()
+        .any(|e| e.specifier == "Role" && e.is_type_export));
+}
+
+#[test]
+fn test_type_export_with_other_alias_is_kept() {
+    let source = r#"export { User } from "./user";
+export type { User as UserType } from "./user";"#;
+    let exports = parse_exports_napi(source.to_string()).unwrap();
+    assert_eq!(remove_duplicates(exports).len(), 2);
+}
+
+#[test]
+fn test_sources_differing_in_dot_segments_are_one_module() {
+    let source = r#"export { Button } from "./button/../Button";
+export { Button } from "./Button";
+export { Button } from "./Button/index";"#;
+    let exports = parse_exports_napi(source.to_string()).unwrap();
+    let sources: Vec<String> = remove_duplicates(exports)
+        .into_iter()
+        .map(|e| e.source)
+        .collect();
+    // `./Button/index` may be another file; only resolution can tell
+    assert_eq!(sources, vec!["./Button", "./Button/index"]);
+}
+
+#[test]
+fn test_sources_that_were_not_merged_keep_their_spelling() {
+    let source = r#"export { Icon } from "./ui/./icons/index.ts";
+export { Spinner } from "./ui/icons";
+export { Other } from "other-package";"#;
+    let exports = parse_exports_napi(source.to_string()).unwrap();
+    let sources: Vec<String> = remove_duplicates(exports)
+        .into_iter()
+        .map(|e| e.source)
+        .collect();
+    assert_eq!(sources, vec!["./ui/./icons/index.ts", "./ui/icons", "other-package"]);
+}
+
+fn dedupe(fixture: &Fixture, source: &str, resolve: bool) -> String {
+    let options =
+        BarrelLoaderOptions { resolve_barrel_exports: Some(resolve), ..Default::default() };
+    fixture.process(source, "src/index.ts", options).code
+}
+
+#[test]
+fn test_spellings_resolving_to_one_file_are_merged() {
+    let fixture = Fixture::in_memory(&[("src/Button/index.ts", "export const Button = 1;\n")]);
+    let source =
+        "export { Button } from \"./Button/index.ts\";\nexport { Button } from \"./Button\";\n";
+
+    assert_eq!(dedupe(&fixture, source, true), "export { Button } from \"./Button\";\n");
+    // Without resolution the spellings are not known to be one module
+    assert_eq!(dedupe(&fixture, source, false), source);
+}
+
+#[test]
+fn test_distinct_files_with_similar_spellings_are_kept() {
+    let fixture = Fixture::in_memory(&[
+        ("src/Button.ts", "export const Button = 1;\n"),
+        ("src/Button/index.ts", "export const Button = 2;\n"),
+    ]);
+    let source =
+        "export { Button } from \"./Button\";\nexport { Button } from \"./Button/index\";\n";
+
+    assert_eq!(dedupe(&fixture, source, true), source);
+}
@@ -0,0 +1,100 @@
+mod common;
+
+use barrel_loader::{BarrelLoaderOptions, DependencyLog, FileSystem, ProcessResult};
+use common::Fixture;
+use std::path::{Path, PathBuf};
+
+const LAYOUT: &[(&str, &str)] = &[
+    ("src/index.ts", "export * from \"./ui\";\n"),
+    ("src/ui/index.ts", "export { Button } from \"./button\";\n"),
+    ("src/ui/button.tsx", "export const Button = 1;\n"),
+];
+
+fn process(fixture: &Fixture, threads: u32) -> ProcessResult {
+    let source = fixture
+        .file_system()
+        .read(Path::new("/app/src/index.ts"))
+        .unwrap();
+    let options = BarrelLoaderOptions {
+        resolve_barrel_exports: Some(true),
+        threads: Some(threads),
+        ..Default::default()
+    };
+    fixture.process(&source, "src/index.ts", options)
+}
+
+fn has(paths: &[String], path: &str) -> bool {
+    paths.iter().any(|p| p == path)
+}
+
+#[test]
+fn test_read_modules_are_file_dependencies() {
+    let result = process(&Fixture::in_memory(LAYOUT), 1);
+
+    assert_eq!(result.code, "export { Button } from \"./ui/button\";\n");
+    assert!(has(&result.file_dependencies, "/app/src/ui/index.ts"));
+    assert!(has(&result.file_dependencies, "/app/src/ui/button.tsx"));
+}
+
+#[test]
+fn test_directories_probed_for_a_module_are_context_dependencies() {
+    let result = process(&Fixture::in_memory(LAYOUT), 1);
+
+    // `button.ts` was probed before `button.tsx`; the tsconfig lookup above is not a context
+    assert_eq!(result.context_dependencies, vec!["/app/src/ui".to_string()]);
+}
+
+#[test]
+fn test_probed_paths_are_missing_dependencies() {
+    let result = process(&Fixture::in_memory(LAYOUT), 1);
+
+    assert!(has(&result.missing_dependencies, "/app/src/ui/button.ts"));
+    assert!(has(&result.missing_dependencies, "/app/src/tsconfig.json"));
+    assert!(!has(&result.missing_dependencies, "/app/src/ui/button.tsx"));
+}
+
+#[test]
+fn test_creating_a_missing_dependency_changes_output() {
+    let before = process(&Fixture::in_memory(LAYOUT), 1);
+    assert!(has(&before.missing_dependencies, "/app/src/ui/button.ts"));
+
+    let fixture =
+        Fixture::in_memory(LAYOUT).with_file("src/ui/button.ts", "export const Button = 2;\n");
+    let after = process(&fixture, 1);
+
+    assert!(has(&after.file_dependencies, "/app/src/ui/button.ts"));
+    assert!(!has(&after.file_dependencies, "/app/src/ui/button.tsx"));
+}
+
+#[test]
+fn test_parallel_reads_are_recorded() {
+    assert_eq!(process(&Fixture::in_memory(LAYOUT), 4), process(&Fixture::in_memory(LAYOUT), 1));
+}
+
+#[test]
+fn test_non_barrel_file_has_no_dependencies() {
+    let result = Fixture::in_memory(LAYOUT).process(
+        "export const a = 1;\n",
+        "src/button.ts",
+        BarrelLoaderOptions::default(),
+    );
+
+    assert_eq!(
+        result,
+        ProcessResult { code: "export const a = 1;\n".to_string(), ..Default::default() }
+    );
+}
+
+#[test]
+fn test_dependency_log_records_listed_directories() {
+    let fixture = Fixture::in_memory(LAYOUT);
+    let log = DependencyLog::new();
+    let tracked = log.track(fixture.file_system());
+
+    assert!(tracked.read_dir(Path::new("/app/src/ui")).is_ok());
+    assert!(tracked.read_dir(Path::new("/app/lib")).is_err());
+
+    assert_eq!(log.contexts(), vec![PathBuf::from("/app/src/ui")]);
+    assert_eq!(log.missing(), vec![PathBuf::from("/app/lib")]);
+    assert!(tracked.as_sync().is_some());
+}
@@ -0,0 +1,131 @@
+mod common;
+
+use barrel_loader::{AffectedBarrel, BarrelLoaderOptions, ExportGraph};
+use common::Fixture;
+use std::path::{Path, PathBuf};
+
+const LAYOUT: &[(&str, &str)] = &[
+    (
+        "src/index.ts",
+        "export * from \"./ui\";\nexport { format } from \"./format\";\n",
+    ),
+    (
+        "src/ui/index.ts",
+        "export { Button } from \"./button\";\nexport * from \"./icon\";\n",
+    ),
+    ("src/ui/button.tsx", "export const Button = 1;\n"),
+    ("src/ui/icon.ts", "export const Icon = 1;\n"),
+    ("src/format.ts", "export const format = 1;\n"),
+];
+
+fn graph(fixture: &Fixture) -> ExportGraph {
+    let fs = fixture.file_system();
+    let mut graph = ExportGraph::new(BarrelLoaderOptions::default());
+    graph
+        .add_barrel(fs, Path::new("/app/src/index.ts"))
+        .unwrap();
+    graph
+        .add_barrel(fs, Path::new("/app/src/ui/index.ts"))
+        .unwrap();
+    graph
+}
+
+fn affected(barrel: &str, names: &[&str]) -> AffectedBarrel {
+    AffectedBarrel {
+        barrel: barrel.to_string(),
+        names: names.iter().map(ToString::to_string).collect(),
+    }
+}
+
+fn paths(paths: &[&str]) -> Vec<PathBuf> {
+    paths.iter().map(PathBuf::from).collect()
+}
+
+#[test]
+fn test_dependents_follow_reexport_edges() {
+    let graph = graph(&Fixture::in_memory(LAYOUT));
+
+    assert_eq!(
+        graph.dependents(Path::new("/app/src/ui/button.tsx")),
+        paths(&["/app/src/index.ts", "/app/src/ui/index.ts"])
+    );
+    assert_eq!(graph.dependents(Path::new("/app/src/format.ts")), paths(&["/app/src/index.ts"]));
+    assert!(graph.dependents(Path::new("/app/src/other.ts")).is_empty());
+}
+
+#[test]
+fn test_changed_leaf_affects_names_it_provides() {
+    let mut graph = graph(&Fixture::in_memory(LAYOUT));
+    let fixture =
+        Fixture::in_memory(LAYOUT).with_file("src/ui/button.tsx", "export const Button = 2;\n");
+
+    assert_eq!(
+        graph.update(fixture.file_system(), &paths(&["/app/src/ui/button.tsx"])),
+        vec![
+            affected("/app/src/index.ts", &["Button"]),
+            affected("/app/src/ui/index.ts", &["Button"]),
+        ]
+    );
+}
+
+#[test]
+fn test_new_export_is_reported_through_star_exports() {
+    let mut graph = graph(&Fixture::in_memory(LAYOUT));
+    let fixture = Fixture::in_memory(LAYOUT)
+        .with_file("src/ui/icon.ts", "export const Icon = 1;\nexport const IconSize = 2;\n");
+
+    let result = graph.update(fixture.file_system(), &paths(&["/app/src/ui/icon.ts"]));
+
+    assert_eq!(result[0], affected("/app/src/index.ts", &["Icon", "IconSize"]));
+    assert_eq!(result[1], affected("/app/src/ui/index.ts", &["Icon", "IconSize"]));
+}
+
+#[test]
+fn test_created_file_that_was_probed_moves_the_export() {
+    let mut graph = graph(&Fixture::in_memory(LAYOUT));
+    let fixture =
+        Fixture::in_memory(LAYOUT).with_file("src/ui/button.ts", "export const Button = 3;\n");
+
+    assert_eq!(graph.dependents(Path::new("/app/src/ui/button.ts")).len(), 2);
+    let result = graph.update(fixture.file_system(), &paths(&["/app/src/ui/button.ts"]));
+
+    assert_eq!(result.len(), 2);
+    assert!(result.iter().all(|barrel| barrel.names == vec!["Button"]));
+    assert!(graph
+        .dependents(Path::new("/app/src/ui/button.tsx"))
+        .is_empty());
+}
+
+#[test]
+fn test_edits_outside_the_graph_affect_nothing() {
+    let mut graph = graph(&Fixture::in_memory(LAYOUT));
+    let fixture = Fixture::in_memory(LAYOUT).with_file("src/other.ts", "export const other = 1;\n");
+
+    assert!(graph
+        .update(fixture.file_system(), &paths(&["/app/src/other.ts"]))
+        .is_empty());
+}
+
+#[test]
+fn test_graph_is_updated_after_each_change() {
+    let mut graph = graph(&Fixture::in_memory(LAYOUT));
+    let renamed =
+        Fixture::in_memory(LAYOUT).with_file("src/format.ts", "export const formatDate = 1;\n");
+    let result = graph.update(renamed.file_system(), &paths(&["/app/src/format.ts"]));
+    assert_eq!(result, vec![affected("/app/src/index.ts", &["format"])]);
+
+    let fixture = renamed.with_file("src/ui/icon.ts", "export const Icon = 2;\n");
+    let result = graph.update(fixture.file_system(), &paths(&["/app/src/ui/icon.ts"]));
+    assert_eq!(result[0], affected("/app/src/index.ts", &["Icon"]));
+}
+
+#[test]
+fn test_removed_barrel_no_longer_depends_on_files() {
+    let mut graph = graph(&Fixture::in_memory(LAYOUT));
+
+    assert!(graph.remove_barrel(Path::new("/app/src/index.ts")));
+    assert!(!graph.remove_barrel(Path::new("/app/src/index.ts")));
+
+    assert_eq!(graph.barrels(), paths(&["/app/src/ui/index.ts"]));
+    assert!(graph.dependents(Path::new("/app/src/format.ts")).is_empty());
+}
@@ -0,0 +1,115 @@
+mod common;
+
+use barrel_loader::{BarrelLoader, BarrelLoaderOptions, FileSystem, Resolver};
+use common::Fixture;
+use std::path::{Path, PathBuf};
+
+const LAYOUT: &[(&str, &str)] = &[
+    ("src/index.ts", "export * from \"./ui\";\nexport { format } from \"@/utils\";\n"),
+    ("src/ui/index.ts", "export { Button } from \"./button\";\n"),
+    ("src/ui/button.tsx", "export const Button = () => null;\n"),
+    ("src/utils/index.ts", "export const format = () => '';\n"),
+    (
+        "tsconfig.json",
+        r#"{ "compilerOptions": { "baseUrl": ".", "paths": { "@/*": ["src/*"] } } }"#,
+    ),
+    ("node_modules/.store/kit/package.json", r#"{ "module": "./index.js" }"#),
+    ("node_modules/.store/kit/index.js", "export const kit = 1;\n"),
+];
+
+fn project() -> Fixture {
+    Fixture::in_memory(LAYOUT).with_symlink("node_modules/kit", ".store/kit")
+}
+
+#[test]
+fn test_memory_file_system_stat_and_read_dir() {
+    let fixture = project();
+    let fs = fixture.file_system();
+
+    assert!(fs.is_file(Path::new("/app/src/ui/button.tsx")));
+    assert!(fs.is_dir(Path::new("/app/src/ui")));
+    assert!(fs.is_dir(Path::new("/app/node_modules/kit")));
+    assert!(fs.stat(Path::new("/app/src/missing.ts")).is_err());
+    assert_eq!(
+        fs.read_dir(Path::new("/app/src")).unwrap(),
+        vec![
+            PathBuf::from("/app/src/index.ts"),
+            PathBuf::from("/app/src/ui"),
+            PathBuf::from("/app/src/utils"),
+        ]
+    );
+}
+
+#[test]
+fn test_memory_file_system_follows_symlinks() {
+    let fixture = project()
+        .with_symlink("/loop/a", "b")
+        .with_symlink("/loop/b", "a");
+    let fs = fixture.file_system();
+
+    assert_eq!(
+        fs.realpath(Path::new("/app/node_modules/kit/index.js"))
+            .unwrap(),
+        PathBuf::from("/app/node_modules/.store/kit/index.js")
+    );
+    assert_eq!(
+        fs.read(Path::new("/app/node_modules/kit/package.json"))
+            .unwrap(),
+        r#"{ "module": "./index.js" }"#
+    );
+    assert!(fs.realpath(Path::new("/loop/a")).is_err());
+}
+
+#[test]
+fn test_resolver_reads_through_file_system() {
+    let fixture = project();
+    let resolver = Resolver::with_file_system(fixture.file_system()).with_packages(true);
+    let importer = Path::new("/app/src/index.ts");
+
+    assert_eq!(
+        resolver.resolve_source(importer, "./ui"),
+        Some(PathBuf::from("/app/src/ui/index.ts"))
+    );
+    assert_eq!(
+        resolver.resolve_source(importer, "kit"),
+        Some(PathBuf::from("/app/node_modules/.store/kit/index.js"))
+    );
+    assert_eq!(resolver.resolve_source(importer, "./missing"), None);
+
+    let flattened: Vec<(String, String)> = resolver
+        .resolve_barrel(importer)
+        .unwrap()
+        .into_iter()
+        .map(|r| (r.export.specifier, r.export.source))
+        .collect();
+    assert_eq!(flattened[0], ("Button".to_string(), "/app/src/ui/button.tsx".to_string()));
+}
+
+#[test]
+fn test_loader_uses_virtual_modules_and_tsconfig() {
+    let source = "export * from \"./ui\";\nexport { format } from \"@/utils\";\n";
+    let options = BarrelLoaderOptions { resolve_barrel_exports: Some(true), ..Default::default() };
+    let output = project()
+        .loader(options)
+        .process(source, "/app/src/index.ts")
+        .unwrap();
+
+    assert_eq!(
+        output,
+        "export { Button } from \"./ui/button\";\nexport { format } from \"./utils\";\n"
+    );
+}
+
+#[test]
+fn test_file_system_borrowed_by_reference() {
+    let fixture = project();
+    let loader =
+        BarrelLoader::new(BarrelLoaderOptions::default()).with_file_system(fixture.file_system());
+
+    assert_eq!(
+        loader
+            .resolve_source("/app/src/index.ts", "@/utils")
+            .unwrap(),
+        Some(PathBuf::from("/app/src/utils/index.ts"))
+    );
+}
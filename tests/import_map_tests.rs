@@ -0,0 +1,83 @@
+mod common;
+
+use barrel_loader::{
+    BarrelLoader, BarrelLoaderOptions, ImportMap, MappedSpecifier, OsFileSystem, Resolver,
+};
+use common::Fixture;
+use rstest::rstest;
+
+fn project() -> Fixture {
+    Fixture::new(&[
+        (
+            "import_map.json",
+            r#"{
+  "imports": {
+    "lib/": "./src/lib/",
+    "lib/special": "./src/special.ts",
+    "preact": "https://esm.sh/preact@10",
+    "std/": "jsr:@std/"
+  },
+  "scopes": {
+    "./src/legacy/": { "lib/": "./src/legacy-lib/" }
+  }
+}"#,
+        ),
+        ("src/lib/button.ts", "export const Button = 1;\n"),
+        ("src/lib/forms/index.ts", "export const Form = 1;\n"),
+        ("src/special.ts", "export const special = 1;\n"),
+        ("src/legacy-lib/button.ts", "export const Button = 0;\n"),
+        ("src/index.ts", "export { Button } from \"lib/button\";\n"),
+        ("src/legacy/index.ts", "export { Button } from \"lib/button\";\n"),
+    ])
+}
+
+#[rstest]
+#[case("src/index.ts", "lib/button", Some("src/lib/button.ts"))]
+#[case("src/index.ts", "lib/forms", Some("src/lib/forms/index.ts"))]
+#[case("src/index.ts", "lib/special", Some("src/special.ts"))]
+#[case("src/legacy/index.ts", "lib/button", Some("src/legacy-lib/button.ts"))]
+#[case("src/index.ts", "preact", None)]
+#[case("src/index.ts", "std/path", None)]
+#[case("src/index.ts", "npm:react", None)]
+#[case("src/index.ts", "https://deno.land/x/mod.ts", None)]
+fn test_import_map_resolution(
+    #[case] importer: &str,
+    #[case] specifier: &str,
+    #[case] expected: Option<&str>,
+) {
+    let fixture = project();
+    let map = ImportMap::load(&OsFileSystem, &fixture.path("import_map.json")).unwrap();
+    let resolver = Resolver::new().with_import_maps(vec![map]);
+    assert_eq!(
+        resolver.resolve_source(&fixture.path(importer), specifier),
+        expected.map(|path| fixture.path(path))
+    );
+}
+
+#[test]
+fn test_url_targets_are_reported_untouched() {
+    let fixture = project();
+    let map = ImportMap::load(&OsFileSystem, &fixture.path("import_map.json")).unwrap();
+    assert_eq!(
+        map.resolve(&fixture.path("src/index.ts"), "std/path"),
+        Some(MappedSpecifier::Url("jsr:@std/path".to_string()))
+    );
+}
+
+#[test]
+fn test_loader_option_applies_import_maps() {
+    let fixture = project();
+    let loader = BarrelLoader::new(BarrelLoaderOptions {
+        resolve_barrel_exports: Some(true),
+        import_maps: Some(vec![fixture.source("import_map.json")]),
+        ..Default::default()
+    });
+    let source = "export { Button } from \"lib/button\";\nexport { h } from \"preact\";\n";
+    let result = loader
+        .process(source, &fixture.source("src/index.ts"))
+        .unwrap();
+    assert_eq!(
+        result,
+        "export { Button } from \"./lib/button\";\nexport { h } from \"preact\";\n"
+    );
+}
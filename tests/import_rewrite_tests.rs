@@ -0,0 +1,149 @@
+mod common;
+
+use barrel_loader::BarrelLoaderOptions;
+use common::Fixture;
+use rstest::rstest;
+
+const LAYOUT: &[(&str, &str)] = &[
+    (
+        "tsconfig.json",
+        r#"{ "compilerOptions": { "baseUrl": ".", "paths": { "@/*": ["src/*"] } } }"#,
+    ),
+    (
+        "src/components/index.ts",
+        "export { Button } from \"./button/Button\";\n\
+         export type { ButtonProps } from \"./button/Button\";\n\
+         export { default as Card } from \"./card\";\n\
+         export * from \"./theme/hooks\";\n\
+         export * as icons from \"./icons\";\n",
+    ),
+    (
+        "src/components/button/Button.tsx",
+        "export const Button = 1;\nexport interface ButtonProps {}\n",
+    ),
+    ("src/components/card/index.tsx", "export default function Card() {}\n"),
+    (
+        "src/components/theme/hooks.ts",
+        "export const useTheme = 1;\nexport const useColor = 2;\n",
+    ),
+    ("src/components/icons.ts", "export const Star = 1;\n"),
+    ("src/utils.ts", "console.log(1);\nexport { Button } from \"./components\";\n"),
+];
+
+fn rewrite(fixture: &Fixture, options: BarrelLoaderOptions, source: &str) -> String {
+    fixture
+        .loader(options)
+        .rewrite_imports(source, "/app/src/app.tsx")
+        .unwrap()
+}
+
+#[rstest]
+#[case::named(
+    "import { Button, useTheme } from \"@/components\";",
+    "import { Button } from \"@/components/button/Button\";\n\
+     import { useTheme } from \"@/components/theme/hooks\";"
+)]
+#[case::aliased(
+    "import { Button as Btn, useColor as color, useTheme } from \"@/components\";",
+    "import { Button as Btn } from \"@/components/button/Button\";\n\
+     import { useColor as color, useTheme } from \"@/components/theme/hooks\";"
+)]
+#[case::default_export(
+    "import { Card } from \"@/components\";",
+    "import Card from \"@/components/card\";"
+)]
+#[case::default_export_renamed(
+    "import { Card as Panel, Button } from \"@/components\";",
+    "import Panel from \"@/components/card\";\nimport { Button } from \"@/components/button/Button\";"
+)]
+#[case::type_only(
+    "import type { ButtonProps } from \"@/components\";",
+    "import type { ButtonProps } from \"@/components/button/Button\";"
+)]
+#[case::inline_type(
+    "import { type ButtonProps, Button } from \"@/components\";",
+    "import { type ButtonProps, Button } from \"@/components/button/Button\";"
+)]
+#[case::namespace_reexport(
+    "import { icons } from \"@/components\";",
+    "import * as icons from \"@/components/icons\";"
+)]
+#[case::relative(
+    "import { Button } from \"./components\";",
+    "import { Button } from \"./components/button/Button\";"
+)]
+fn test_rewrite_imports_past_barrel(#[case] source: &str, #[case] expected: &str) {
+    assert_eq!(
+        rewrite(&Fixture::in_memory(LAYOUT), BarrelLoaderOptions::default(), source),
+        expected
+    );
+}
+
+#[rstest]
+#[case::namespace_import("import * as components from \"@/components\";")]
+#[case::missing_default("import Components from \"@/components\";")]
+#[case::not_a_barrel("import { Button } from \"./utils\";")]
+#[case::side_effect_only("import \"@/components\";")]
+#[case::unresolved("import { Button } from \"@/missing\";")]
+#[case::template_literal("const code = `\nimport { Button } from \"@/components\";\n`;\n")]
+#[case::block_comment("/*\nimport { Button } from \"@/components\";\n*/\n")]
+fn test_imports_left_as_written(#[case] source: &str) {
+    assert_eq!(
+        rewrite(&Fixture::in_memory(LAYOUT), BarrelLoaderOptions::default(), source),
+        source
+    );
+}
+
+#[test]
+fn test_untraceable_names_stay_on_barrel_import() {
+    let source = "import { Button, Missing, type Unknown } from \"@/components\";";
+    assert_eq!(
+        rewrite(&Fixture::in_memory(LAYOUT), BarrelLoaderOptions::default(), source),
+        "import { Button } from \"@/components/button/Button\";\n\
+         import { Missing, type Unknown } from \"@/components\";"
+    );
+}
+
+#[test]
+fn test_multiline_import_keeps_layout_and_surrounding_code() {
+    let source = "// header\n  import {\n    Button,\n    useTheme,\n  } from '@/components'\nconst a = Button;\n";
+    assert_eq!(
+        rewrite(&Fixture::in_memory(LAYOUT), BarrelLoaderOptions::default(), source),
+        "// header\n  import { Button } from '@/components/button/Button'\n  \
+         import { useTheme } from '@/components/theme/hooks'\nconst a = Button;\n"
+    );
+}
+
+#[test]
+fn test_relative_imports_follow_source_style() {
+    let options = BarrelLoaderOptions {
+        source_style: Some("fully-specified".to_string()),
+        ..Default::default()
+    };
+    assert_eq!(
+        rewrite(&Fixture::in_memory(LAYOUT), options, "import { Card } from \"./components\";"),
+        "import Card from \"./components/card/index.js\";"
+    );
+}
+
+#[rstest]
+#[case::deep_imports_allowed(
+    r#"{ "name": "@acme/ui", "main": "./src/index.ts" }"#,
+    "@acme/ui/src/button"
+)]
+#[case::subpath_not_exported(
+    r#"{ "name": "@acme/ui", "exports": { ".": "./src/index.ts" } }"#,
+    "@acme/ui"
+)]
+fn test_package_barrels_use_subpaths_that_resolve(#[case] manifest: &str, #[case] expected: &str) {
+    let fixture = Fixture::in_memory(LAYOUT)
+        .with_file("node_modules/@acme/ui/package.json", manifest)
+        .with_file("node_modules/@acme/ui/src/index.ts", "export { Button } from \"./button\";\n")
+        .with_file("node_modules/@acme/ui/src/button.ts", "export const Button = 1;\n");
+    let options = BarrelLoaderOptions { resolve_packages: Some(true), ..Default::default() };
+
+    assert_eq!(
+        rewrite(&fixture, options, "import { Button } from \"@acme/ui\";"),
+        format!("import {{ Button }} from \"{expected}\";")
+    );
+}
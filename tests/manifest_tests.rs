@@ -0,0 +1,92 @@
+mod common;
+
+use barrel_loader::{
+    BarrelLoaderOptions, ExportManifest, ManifestEntry, ManifestStar, MANIFEST_VERSION,
+};
+use common::Fixture;
+
+const BARREL: &str = "export { Button as PrimaryButton } from \"./ui\";\n\
+                      export type { ButtonProps } from \"./ui\";\n\
+                      export { default as Card } from \"./card\";\n\
+                      export * as icons from \"./icons\";\n\
+                      export * from \"./hooks\";\n\
+                      export * from \"some-package\";\n\
+                      export const version = 1;\n";
+
+const LAYOUT: &[(&str, &str)] = &[
+    ("src/ui/index.ts",
+            "import \"./ui.css\";\nexport { Button } from \"./button\";\nexport type { ButtonProps } from \"./button\";\n"),
+    ("src/ui/button.ts",
+            "export const Button = 1;\nexport interface ButtonProps {}\n"),
+    ("src/card.ts", "export default function Card() {}\n"),
+    ("src/icons.ts", "export const Star = 1;\n"),
+    ("src/hooks/index.ts", "export * from \"./theme\";\n"),
+    ("src/hooks/theme.ts", "export type Theme = string;\n"),
+];
+
+fn process(manifest: Option<bool>) -> (String, Option<String>) {
+    let options =
+        BarrelLoaderOptions { manifest, resolve_barrel_exports: Some(true), ..Default::default() };
+    let result = Fixture::in_memory(LAYOUT).process(BARREL, "src/index.ts", options);
+    (result.code, result.manifest)
+}
+
+fn entry(file: &str, local: &str, kind: &str, via: &[&str]) -> ManifestEntry {
+    ManifestEntry {
+        file: Some(file.to_string()),
+        local: local.to_string(),
+        kind: kind.to_string(),
+        via: via.iter().map(ToString::to_string).collect(),
+    }
+}
+
+#[test]
+fn test_manifest_traces_every_export_to_its_declaration() {
+    let (_, manifest) = process(Some(true));
+    let manifest: ExportManifest = serde_json::from_str(&manifest.unwrap()).unwrap();
+
+    assert_eq!(manifest.version, MANIFEST_VERSION);
+    assert_eq!(manifest.barrel, "/app/src/index.ts");
+    let names: Vec<&str> = manifest.exports.keys().map(String::as_str).collect();
+    assert_eq!(
+        names,
+        [
+            "ButtonProps",
+            "Card",
+            "PrimaryButton",
+            "Theme",
+            "icons",
+            "version"
+        ]
+    );
+    assert_eq!(
+        manifest.exports["PrimaryButton"],
+        entry("/app/src/ui/button.ts", "Button", "value", &["/app/src/ui/index.ts"])
+    );
+    assert_eq!(
+        manifest.exports["ButtonProps"],
+        entry("/app/src/ui/button.ts", "ButtonProps", "type", &["/app/src/ui/index.ts"])
+    );
+    assert_eq!(manifest.exports["Card"], entry("/app/src/card.ts", "default", "value", &[]));
+    assert_eq!(manifest.exports["icons"], entry("/app/src/icons.ts", "*", "value", &[]));
+    assert_eq!(
+        manifest.exports["Theme"],
+        entry("/app/src/hooks/theme.ts", "Theme", "type", &["/app/src/hooks/index.ts"])
+    );
+    assert_eq!(manifest.exports["version"], entry("/app/src/index.ts", "version", "value", &[]));
+    assert_eq!(
+        manifest.stars,
+        vec![ManifestStar { source: "some-package".to_string(), file: None }]
+    );
+}
+
+#[test]
+fn test_manifest_does_not_change_the_code() {
+    let (with_manifest, _) = process(Some(true));
+    let (without_manifest, manifest) = process(None);
+
+    assert_eq!(manifest, None);
+    assert_eq!(with_manifest, without_manifest);
+    // The emitted code still stops at the module with side effects
+    assert!(with_manifest.contains("export { Button as PrimaryButton } from \"./ui\""));
+}
@@ -0,0 +1,170 @@
+mod common;
+
+use barrel_loader::{BarrelLoader, BarrelLoaderOptions, ModuleCache, Resolver};
+use common::Fixture;
+use std::fs;
+use std::path::Path;
+
+fn project() -> Fixture {
+    Fixture::new(&[
+        (
+            "src/index.ts",
+            "export * from \"./ui\";\nexport { format } from \"./format\";\n",
+        ),
+        ("src/ui/index.ts", "export { Button } from \"./button\";\n"),
+        ("src/ui/button.ts", "export const Button = 1;\n"),
+        ("src/format.ts", "export const format = 1;\n"),
+    ])
+}
+
+fn specifiers(resolver: &Resolver, fixture: &Fixture) -> Vec<String> {
+    resolver
+        .resolve_barrel(&fixture.path("src/index.ts"))
+        .unwrap()
+        .into_iter()
+        .map(|r| r.export.specifier)
+        .collect()
+}
+
+fn cache_files(dir: &Path) -> Vec<String> {
+    fs::read_dir(dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+        .collect()
+}
+
+#[test]
+fn test_warm_build_reads_analysis_from_cache() {
+    let fixture = project();
+    let dir = fixture.path("cache");
+
+    let cold = Resolver::new().with_cache(ModuleCache::new(&dir));
+    assert_eq!(specifiers(&cold, &fixture), vec!["Button", "format"]);
+    let stats = ModuleCache::new(&dir).stats();
+    assert_eq!((stats.hits, stats.misses, stats.writes, stats.entries), (0, 4, 4, 4));
+
+    let warm = Resolver::new().with_cache(ModuleCache::new(&dir));
+    assert_eq!(specifiers(&warm, &fixture), vec!["Button", "format"]);
+    let stats = ModuleCache::new(&dir).stats();
+    assert_eq!((stats.hits, stats.misses, stats.entries), (4, 4, 4));
+    assert!(stats.bytes > 0.0);
+}
+
+#[test]
+fn test_changed_file_is_parsed_again() {
+    let fixture = project();
+    let dir = fixture.path("cache");
+    let _ = specifiers(&Resolver::new().with_cache(ModuleCache::new(&dir)), &fixture);
+
+    fixture.write("src/ui/index.ts", "export { Button, Icon } from \"./button\";\n");
+    let warm = Resolver::new().with_cache(ModuleCache::new(&dir));
+
+    assert_eq!(specifiers(&warm, &fixture), vec!["Button", "Icon", "format"]);
+    let stats = ModuleCache::new(&dir).stats();
+    assert_eq!((stats.hits, stats.misses), (3, 5));
+}
+
+#[test]
+fn test_unreadable_entries_are_replaced() {
+    let fixture = project();
+    let dir = fixture.path("cache");
+    let _ = specifiers(&Resolver::new().with_cache(ModuleCache::new(&dir)), &fixture);
+    for name in cache_files(&dir) {
+        fs::write(dir.join(name), "{ \"version\": \"0.0.0\"").unwrap();
+    }
+
+    let warm = Resolver::new().with_cache(ModuleCache::new(&dir));
+    assert_eq!(specifiers(&warm, &fixture), vec!["Button", "format"]);
+    let stats = ModuleCache::new(&dir).stats();
+    assert_eq!((stats.hits, stats.misses, stats.writes), (0, 8, 8));
+}
+
+#[test]
+fn test_entry_of_other_contents_is_not_used() {
+    let fixture = project();
+    let button = fixture.path("button-cache");
+    let format = fixture.path("format-cache");
+    let _ = Resolver::new()
+        .with_cache(ModuleCache::new(&button))
+        .load(&fixture.path("src/ui/button.ts"));
+    let _ = Resolver::new()
+        .with_cache(ModuleCache::new(&format))
+        .load(&fixture.path("src/format.ts"));
+
+    // Simulate a file name hash collision between the two contents
+    let [button_entry] = &cache_files(&button)[..] else {
+        panic!("one entry expected")
+    };
+    let [format_entry] = &cache_files(&format)[..] else {
+        panic!("one entry expected")
+    };
+    fs::copy(button.join(button_entry), format.join(format_entry)).unwrap();
+
+    let module = Resolver::new()
+        .with_cache(ModuleCache::new(&format))
+        .load(&fixture.path("src/format.ts"))
+        .unwrap();
+    assert_eq!(module.declarations[0].name, "format");
+    let stats = ModuleCache::new(&format).stats();
+    assert_eq!((stats.hits, stats.misses), (0, 2));
+}
+
+#[test]
+fn test_eviction_keeps_directory_under_limit() {
+    let fixture = project();
+    let dir = fixture.path("cache");
+    let _ = specifiers(&Resolver::new().with_cache(ModuleCache::new(&dir)), &fixture);
+    let total = ModuleCache::new(&dir).stats().bytes;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let cache = ModuleCache::new(&dir).with_max_size((total / 2.0) as u64);
+    let evicted = cache.evict().unwrap();
+
+    let stats = cache.stats();
+    assert!(evicted >= 2);
+    assert_eq!(stats.evictions as usize, evicted);
+    assert_eq!(stats.entries as usize, 4 - evicted);
+    assert!(stats.bytes <= total / 2.0);
+}
+
+#[test]
+fn test_concurrent_writers_leave_one_complete_entry() {
+    let fixture = project();
+    let dir = fixture.path("cache");
+    std::thread::scope(|scope| {
+        for _ in 0..8 {
+            scope.spawn(|| {
+                let resolver = Resolver::new().with_cache(ModuleCache::new(&dir));
+                let module = resolver.load(&fixture.path("src/index.ts")).unwrap();
+                assert_eq!(module.exports.len(), 2);
+            });
+        }
+    });
+
+    assert_eq!(cache_files(&dir).len(), 1);
+    let cached = Resolver::new().with_cache(ModuleCache::new(&dir));
+    let module = cached.load(&fixture.path("src/index.ts")).unwrap();
+    assert_eq!(module.exports[1].source, "./format");
+}
+
+#[test]
+fn test_loader_option_enables_cache() {
+    let fixture = project();
+    let source = fs::read_to_string(fixture.path("src/index.ts")).unwrap();
+    let loader = BarrelLoader::new(BarrelLoaderOptions {
+        resolve_barrel_exports: Some(true),
+        cache_directory: Some(fixture.source("cache")),
+        ..Default::default()
+    });
+
+    let cold = loader
+        .process(&source, &fixture.source("src/index.ts"))
+        .unwrap();
+    let warm = loader
+        .process(&source, &fixture.source("src/index.ts"))
+        .unwrap();
+
+    assert_eq!(cold, warm);
+    let stats = ModuleCache::new(fixture.path("cache")).stats();
+    assert_eq!((stats.misses, stats.hits), (3, 3));
+}
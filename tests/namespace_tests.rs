@@ -0,0 +1,101 @@
+mod common;
+
+use barrel_loader::{BarrelLoader, BarrelLoaderOptions};
+use common::Fixture;
+
+fn convert(fixture: &Fixture, barrel: &str) -> String {
+    let loader = BarrelLoader::new(BarrelLoaderOptions {
+        convert_namespace_to_named: Some(true),
+        ..Default::default()
+    });
+    let source = std::fs::read_to_string(fixture.path(barrel)).unwrap();
+    loader.process(&source, &fixture.source(barrel)).unwrap()
+}
+
+#[test]
+fn test_star_export_expanded_to_named_and_type_exports() {
+    let fixture = Fixture::new(&[
+        ("index.ts", "export * from \"./user\";\n"),
+        (
+            "user.ts",
+            "export interface User {}\nexport type Role = string;\nexport const createUser = () => ({});\nexport default createUser;\n",
+        ),
+    ]);
+    assert_eq!(
+        convert(&fixture, "index.ts"),
+        "export type { User, Role } from \"./user\";\nexport { createUser } from \"./user\";\n"
+    );
+}
+
+#[test]
+fn test_star_export_follows_nested_stars_and_re_exports() {
+    let fixture = Fixture::new(&[
+        ("index.ts", "export * from \"./a\";\n"),
+        (
+            "a.ts",
+            "export * from \"./b\";\nexport { c } from \"./c\";\nexport function a() {}\n",
+        ),
+        ("b.ts", "export const b = 1;\nexport { default as B } from \"./c\";\n"),
+        ("c.ts", "export const c = 1;\nexport default c;\n"),
+    ]);
+    assert_eq!(convert(&fixture, "index.ts"), "export { a, c, b, B } from \"./a\";\n");
+}
+
+#[test]
+fn test_explicit_exports_shadow_and_ambiguous_names_are_dropped() {
+    let fixture = Fixture::new(&[
+        (
+            "index.ts",
+            "export { Button } from \"./button\";\nexport * from \"./a\";\nexport * from \"./b\";\n",
+        ),
+        ("button.ts", "export const Button = 1;\n"),
+        ("a.ts", "export const Button = 2;\nexport const Shared = 1;\nexport const OnlyA = 1;\n"),
+        ("b.ts", "export const Shared = 2;\nexport const OnlyB = 1;\n"),
+    ]);
+    assert_eq!(
+        convert(&fixture, "index.ts"),
+        "export { Button } from \"./button\";\nexport { OnlyA } from \"./a\";\nexport { OnlyB } from \"./b\";\n"
+    );
+}
+
+#[test]
+fn test_same_binding_through_two_stars_is_not_ambiguous() {
+    let fixture = Fixture::new(&[
+        ("index.ts", "export * from \"./a\";\nexport * from \"./b\";\n"),
+        ("a.ts", "export { shared } from \"./shared\";\n"),
+        ("b.ts", "export * from \"./shared\";\n"),
+        ("shared.ts", "export const shared = 1;\n"),
+    ]);
+    assert_eq!(convert(&fixture, "index.ts"), "export { shared } from \"./a\";\n");
+}
+
+#[test]
+fn test_unanalyzable_modules_fall_back_to_star_export() {
+    let fixture = Fixture::new(&[
+        (
+            "index.ts",
+            "export * from \"./multiline\";\nexport * from \"./external\";\nexport * from \"./missing\";\n",
+        ),
+        ("multiline.ts", "export {\n  a,\n  b,\n} from \"./a\";\n"),
+        ("external.ts", "export * from \"react\";\n"),
+    ]);
+    assert_eq!(
+        convert(&fixture, "index.ts"),
+        "export * from \"./multiline\";\nexport * from \"./external\";\nexport * from \"./missing\";\n"
+    );
+}
+
+#[test]
+fn test_every_declarator_of_a_variable_statement_is_exported() {
+    let fixture = Fixture::new(&[
+        ("index.ts", "export * from \"./values\";\n"),
+        (
+            "values.ts",
+            "export const a = 1, b = 2;\nexport let c = { d: 1, e: [1, 2] },\n  f = \"x, y\",\n  { g, h: i } = c;\n",
+        ),
+    ]);
+    assert_eq!(
+        convert(&fixture, "index.ts"),
+        "export { a, b, c, f, g, i } from \"./values\";\n"
+    );
+}
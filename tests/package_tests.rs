@@ -0,0 +1,71 @@
+mod common;
+
+use barrel_loader::{BarrelLoader, BarrelLoaderOptions, Resolver};
+use common::Fixture;
+use rstest::rstest;
+
+fn workspace() -> Fixture {
+    Fixture::new(&[
+        (
+            "node_modules/@acme/ui/package.json",
+            r#"{
+  "name": "@acme/ui",
+  "exports": {
+    ".": { "require": "./dist/index.cjs", "import": "./src/index.ts" },
+    "./icons/*": { "types": "./src/icons/*.d.ts", "default": "./src/icons/*.ts" },
+    "./internal/*": null
+  }
+}"#,
+        ),
+        ("node_modules/@acme/ui/src/index.ts", "export { Button } from \"./button\";\n"),
+        ("node_modules/@acme/ui/src/button.ts", "export const Button = 1;\n"),
+        ("node_modules/@acme/ui/src/icons/star.ts", "export const Star = 1;\n"),
+        (
+            "node_modules/utils/package.json",
+            r#"{ "name": "utils", "module": "./esm/index.js", "main": "./cjs/index.js" }"#,
+        ),
+        ("node_modules/utils/esm/index.js", "export const clamp = () => 0;\n"),
+        ("node_modules/utils/cjs/index.js", "exports.clamp = () => 0;\n"),
+        ("node_modules/utils/math.js", "export const add = () => 0;\n"),
+        ("apps/web/src/index.ts", "export * from \"@acme/ui\";\n"),
+    ])
+}
+
+#[rstest]
+#[case("@acme/ui", Some("node_modules/@acme/ui/src/index.ts"))]
+#[case("@acme/ui/icons/star", Some("node_modules/@acme/ui/src/icons/star.ts"))]
+#[case("@acme/ui/internal/secret", None)]
+#[case("@acme/ui/button", None)]
+#[case("utils", Some("node_modules/utils/esm/index.js"))]
+#[case("utils/math", Some("node_modules/utils/math.js"))]
+#[case("missing-package", None)]
+fn test_resolve_package_specifiers(#[case] specifier: &str, #[case] expected: Option<&str>) {
+    let fixture = workspace();
+    let resolver = Resolver::new().with_packages(true);
+    let importer = fixture.path("apps/web/src/index.ts");
+    assert_eq!(
+        resolver.resolve_source(&importer, specifier),
+        expected.map(|path| fixture.path(path))
+    );
+}
+
+#[test]
+fn test_workspace_barrel_flattened_across_packages_when_enabled() {
+    let fixture = workspace();
+    let source = std::fs::read_to_string(fixture.path("apps/web/src/index.ts")).unwrap();
+    let process = |resolve_packages: Option<bool>| {
+        BarrelLoader::new(BarrelLoaderOptions {
+            resolve_barrel_exports: Some(true),
+            resolve_packages,
+            ..Default::default()
+        })
+        .process(&source, &fixture.source("apps/web/src/index.ts"))
+        .unwrap()
+    };
+
+    assert_eq!(process(None), "export * from \"@acme/ui\";\n");
+    assert_eq!(
+        process(Some(true)),
+        "export { Button } from \"../../../node_modules/@acme/ui/src/button\";\n"
+    );
+}
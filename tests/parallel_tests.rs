@@ -0,0 +1,144 @@
+use barrel_loader::{
+    BarrelLoader, BarrelLoaderOptions, FileSystem, MemoryFileSystem, Metadata, Resolver,
+};
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// A root barrel over `groups` nested barrels of `leaves` modules each
+fn design_system(groups: usize, leaves: usize) -> MemoryFileSystem {
+    let mut fs = MemoryFileSystem::new();
+    let mut root = String::new();
+    for group in 0..groups {
+        let _ = writeln!(root, "export * from \"./group{group}\";");
+        let mut barrel = String::new();
+        for leaf in 0..leaves {
+            let _ = writeln!(barrel, "export {{ C{group}x{leaf} }} from \"./c{leaf}\";");
+            fs.insert_file(
+                format!("/ds/group{group}/c{leaf}.ts"),
+                &format!("export const C{group}x{leaf} = {leaf};\n"),
+            );
+        }
+        fs.insert_file(format!("/ds/group{group}/index.ts"), &barrel);
+    }
+    fs.insert_file("/ds/index.ts", &root);
+    fs
+}
+
+/// Counts module reads per path; `shared` controls whether it may be read from threads
+struct CountingFileSystem {
+    inner: MemoryFileSystem,
+    reads: Mutex<HashMap<PathBuf, usize>>,
+    shared: bool,
+}
+
+impl CountingFileSystem {
+    fn new(inner: MemoryFileSystem, shared: bool) -> Self {
+        Self { inner, reads: Mutex::default(), shared }
+    }
+
+    fn max_reads(&self) -> usize {
+        self.reads
+            .lock()
+            .unwrap()
+            .values()
+            .copied()
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+impl FileSystem for CountingFileSystem {
+    fn read(&self, path: &Path) -> io::Result<String> {
+        let contents = self.inner.read(path)?;
+        *self
+            .reads
+            .lock()
+            .unwrap()
+            .entry(path.to_path_buf())
+            .or_default() += 1;
+        Ok(contents)
+    }
+
+    fn stat(&self, path: &Path) -> io::Result<Metadata> {
+        self.inner.stat(path)
+    }
+
+    fn realpath(&self, path: &Path) -> io::Result<PathBuf> {
+        self.inner.realpath(path)
+    }
+
+    fn read_dir(&self, path: &Path) -> io::Result<Vec<PathBuf>> {
+        self.inner.read_dir(path)
+    }
+
+    fn as_sync(&self) -> Option<&(dyn FileSystem + Sync)> {
+        self.shared.then_some(self as &(dyn FileSystem + Sync))
+    }
+}
+
+fn process(fs: &CountingFileSystem, threads: u32) -> String {
+    let source = fs.inner.read(Path::new("/ds/index.ts")).unwrap();
+    BarrelLoader::new(BarrelLoaderOptions {
+        resolve_barrel_exports: Some(true),
+        threads: Some(threads),
+        ..Default::default()
+    })
+    .with_file_system(fs)
+    .process(&source, "/ds/index.ts")
+    .unwrap()
+}
+
+#[test]
+fn test_parallel_output_matches_sequential_output() {
+    let sequential = process(&CountingFileSystem::new(design_system(12, 25), true), 1);
+    for threads in [2, 4, 16] {
+        let fs = CountingFileSystem::new(design_system(12, 25), true);
+        assert_eq!(process(&fs, threads), sequential);
+    }
+    assert_eq!(sequential.lines().count(), 300);
+    assert!(sequential.starts_with("export { C0x0 } from \"./group0/c0\";\n"));
+}
+
+#[test]
+fn test_prefetch_reads_each_module_once() {
+    let fs = CountingFileSystem::new(design_system(6, 10), true);
+    let output = process(&fs, 8);
+
+    assert_eq!(output.lines().count(), 60);
+    assert_eq!(fs.reads.lock().unwrap().len(), 66);
+    assert_eq!(fs.max_reads(), 1);
+}
+
+#[test]
+fn test_file_system_without_thread_support_resolves_sequentially() {
+    let fs = CountingFileSystem::new(design_system(3, 4), false);
+    let expected = process(&CountingFileSystem::new(design_system(3, 4), true), 4);
+
+    assert_eq!(process(&fs, 4), expected);
+}
+
+#[test]
+fn test_prefetched_resolver_gives_same_exports() {
+    let barrel = Path::new("/ds/index.ts");
+    let cold = Resolver::with_file_system(design_system(4, 5));
+    let warm = Resolver::with_file_system(design_system(4, 5));
+    let exports = warm.load(barrel).unwrap().exports.clone();
+    warm.prefetch(barrel, &exports, 4);
+
+    assert_eq!(warm.resolve_barrel(barrel).unwrap(), cold.resolve_barrel(barrel).unwrap());
+}
+
+#[test]
+fn test_prefetch_stops_at_modules_with_side_effects() {
+    let mut inner = design_system(1, 3);
+    let barrel = inner.read(Path::new("/ds/group0/index.ts")).unwrap();
+    inner.insert_file("/ds/group0/index.ts", &format!("import \"./theme.css\";\n{barrel}"));
+    let fs = CountingFileSystem::new(inner, true);
+
+    assert_eq!(process(&fs, 4), "export * from \"./group0\";\n");
+    let read: Vec<PathBuf> = fs.reads.lock().unwrap().keys().cloned().collect();
+    assert_eq!(read, vec![PathBuf::from("/ds/group0/index.ts")]);
+}
@@ -0,0 +1,106 @@
+mod common;
+
+use barrel_loader::BarrelLoaderOptions;
+use common::Fixture;
+use rstest::rstest;
+
+const BARREL: &str = "export { Button, Icon } from \"./button\";\n\
+                      export type { ButtonProps } from \"./button\";\n\
+                      export { default as Card } from \"./card\";\n\
+                      export * from \"./hooks\";\n\
+                      export { Theme } from \"./theme\";\n";
+
+const LAYOUT: &[(&str, &str)] = &[
+    (
+        "src/button.ts",
+        "export const Button = 1;\nexport const Icon = 2;\nexport interface ButtonProps {}\n",
+    ),
+    ("src/card.ts", "export default 1;\n"),
+    ("src/hooks.ts", "export const useTheme = 1;\n"),
+    ("src/theme.ts", "import \"./theme.css\";\nexport const Theme = 1;\n"),
+];
+
+fn prune(used: &[&str], keep_type_exports: Option<bool>) -> String {
+    let options = BarrelLoaderOptions {
+        used_exports: Some(used.iter().map(ToString::to_string).collect()),
+        keep_type_exports,
+        remove_duplicates: Some(false),
+        ..Default::default()
+    };
+    Fixture::in_memory(LAYOUT)
+        .process(BARREL, "src/index.ts", options)
+        .code
+}
+
+#[rstest]
+#[case::keeps_types_by_default(
+    &["Button"],
+    None,
+    "export { Button } from \"./button\";\n\
+     export type { ButtonProps } from \"./button\";\n\
+     export * from \"./hooks\";\n\
+     export { Theme } from \"./theme\";\n"
+)]
+#[case::drops_types(
+    &["Icon", "Card"],
+    Some(false),
+    "export { Icon } from \"./button\";\n\
+     export { default as Card } from \"./card\";\n\
+     export * from \"./hooks\";\n\
+     export { Theme } from \"./theme\";\n"
+)]
+fn test_prunes_to_used_exports(
+    #[case] used: &[&str],
+    #[case] keep_type_exports: Option<bool>,
+    #[case] expected: &str,
+) {
+    assert_eq!(prune(used, keep_type_exports), expected);
+}
+
+#[test]
+fn test_pruning_everything_leaves_an_empty_module() {
+    let fixture = Fixture::in_memory(LAYOUT).with_file("src/theme.ts", "export const Theme = 1;\n");
+    let options = BarrelLoaderOptions {
+        used_exports: Some(Vec::new()),
+        keep_type_exports: Some(false),
+        ..Default::default()
+    };
+    let source = "import \"./polyfills\";\nexport { Button } from \"./button\";\n";
+    let output = fixture.process(source, "src/index.ts", options).code;
+
+    assert_eq!(output, "import \"./polyfills\";\nexport {};\n");
+}
+
+#[test]
+fn test_pruning_everything_keeps_statements_after_the_exports() {
+    let options = BarrelLoaderOptions {
+        used_exports: Some(Vec::new()),
+        keep_type_exports: Some(false),
+        ..Default::default()
+    };
+    let source = "export { Button } from \"./button\"; const ready = true;\nconsole.log('export { x } from \"./x\"');\nexport * as icons from \"./button\";\nwindow.ready = ready;\n";
+    let output = Fixture::in_memory(LAYOUT)
+        .process(source, "src/index.ts", options)
+        .code;
+
+    assert_eq!(
+        output,
+        "const ready = true;\nconsole.log('export { x } from \"./x\"');\nwindow.ready = ready;\nexport {};\n"
+    );
+}
+
+#[test]
+fn test_pruning_applies_to_flattened_exports() {
+    let fixture = Fixture::in_memory(LAYOUT)
+        .with_file("src/ui/index.ts", "export { Button, Icon } from \"../button\";\n");
+    let options = BarrelLoaderOptions {
+        used_exports: Some(vec!["Icon".to_string()]),
+        resolve_barrel_exports: Some(true),
+        ..Default::default()
+    };
+    let output = fixture
+        .process("export * from \"./ui\";\n", "src/index.ts", options)
+        .code;
+
+    assert_eq!(output, "export { Icon } from \"./button\";\n");
+}
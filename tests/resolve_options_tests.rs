@@ -0,0 +1,173 @@
+mod common;
+
+use barrel_loader::{
+    BarrelLoader, BarrelLoaderOptions, ModuleResolution, ResolveOptions, Resolver,
+};
+use common::Fixture;
+use rstest::rstest;
+
+fn project() -> Fixture {
+    Fixture::new(&[
+        ("src/index.ts", "export * from \"./button.js\";\n"),
+        ("src/button.ts", "export const Button = 1;\n"),
+        ("src/legacy.cts", "export const legacy = 1;\n"),
+        ("src/esm.mts", "export const esm = 1;\n"),
+        ("src/card.vue", "export const Card = 1;\n"),
+        ("src/card.ts", "export const CardTs = 1;\n"),
+        ("src/widgets/main.ts", "export const Widget = 1;\n"),
+        ("src/widgets/index.ts", "export const WidgetIndex = 1;\n"),
+        ("src/lib/package.json", r#"{ "module": "./entry.ts" }"#),
+        ("src/lib/entry.ts", "export const lib = 1;\n"),
+        ("src/lib/index.ts", "export const libIndex = 1;\n"),
+        ("src/output.js", "export const output = 1;\n"),
+        ("src/data.json", "{}\n"),
+        (
+            "node_modules/pkg/package.json",
+            r#"{ "exports": { "source": "./src/index.ts", "default": "./dist/index.js" }, "browser": "./browser.js" }"#,
+        ),
+        ("node_modules/pkg/src/index.ts", "export const pkg = 1;\n"),
+        ("node_modules/pkg/dist/index.js", "export const pkg = 1;\n"),
+        (
+            "node_modules/plain/package.json",
+            r#"{ "main": "./main.js", "browser": "./browser.js" }"#,
+        ),
+        ("node_modules/plain/main.js", "export const plain = 1;\n"),
+        ("node_modules/plain/browser.js", "export const plain = 1;\n"),
+    ])
+}
+
+fn strings(values: &[&str]) -> Vec<String> {
+    values.iter().map(|value| (*value).to_string()).collect()
+}
+
+#[rstest]
+#[case("./legacy", Some("src/legacy.cts"))]
+#[case("./esm", Some("src/esm.mts"))]
+#[case("./card", Some("src/card.ts"))]
+#[case("./lib", Some("src/lib/entry.ts"))]
+#[case("./widgets", Some("src/widgets/index.ts"))]
+#[case("./button.js", None)]
+#[case("./output.js", Some("src/output.js"))]
+#[case("./card.vue", Some("src/card.vue"))]
+#[case("./data.json", Some("src/data.json"))]
+fn test_default_resolve_options(#[case] specifier: &str, #[case] expected: Option<&str>) {
+    let fixture = project();
+    let resolver = Resolver::new();
+    assert_eq!(
+        resolver.resolve_source(&fixture.path("src/index.ts"), specifier),
+        expected.map(|path| fixture.path(path))
+    );
+}
+
+#[rstest]
+#[case(ModuleResolution::Node16, "./button.js", Some("src/button.ts"))]
+#[case(ModuleResolution::Bundler, "./esm.mjs", Some("src/esm.mts"))]
+#[case(ModuleResolution::Bundler, "./legacy.cjs", Some("src/legacy.cts"))]
+#[case(ModuleResolution::Bundler, "./output.js", Some("src/output.js"))]
+#[case(ModuleResolution::Node10, "./button.js", None)]
+fn test_module_resolution_maps_js_to_ts(
+    #[case] mode: ModuleResolution,
+    #[case] specifier: &str,
+    #[case] expected: Option<&str>,
+) {
+    let fixture = project();
+    let resolver =
+        Resolver::new().with_options(ResolveOptions::default().with_module_resolution(mode));
+    assert_eq!(
+        resolver.resolve_source(&fixture.path("src/index.ts"), specifier),
+        expected.map(|path| fixture.path(path))
+    );
+}
+
+#[test]
+fn test_custom_extensions_and_main_files() {
+    let fixture = project();
+    let resolver = Resolver::new().with_options(ResolveOptions {
+        extensions: strings(&[".vue", ".ts"]),
+        main_files: strings(&["main"]),
+        main_fields: Vec::new(),
+        ..ResolveOptions::default()
+    });
+    let importer = fixture.path("src/index.ts");
+
+    assert_eq!(resolver.resolve_source(&importer, "./card"), Some(fixture.path("src/card.vue")));
+    assert_eq!(
+        resolver.resolve_source(&importer, "./widgets"),
+        Some(fixture.path("src/widgets/main.ts"))
+    );
+    assert_eq!(
+        resolver.resolve_source(&importer, "./lib"),
+        None,
+        "main fields are disabled and no main file matches"
+    );
+}
+
+#[test]
+fn test_condition_names_and_main_fields_for_packages() {
+    let fixture = project();
+    let importer = fixture.path("src/index.ts");
+    let resolver =
+        |options: ResolveOptions| Resolver::new().with_packages(true).with_options(options);
+
+    let defaults = resolver(ResolveOptions::default());
+    assert_eq!(
+        defaults.resolve_source(&importer, "pkg"),
+        Some(fixture.path("node_modules/pkg/dist/index.js"))
+    );
+    assert_eq!(
+        defaults.resolve_source(&importer, "plain"),
+        Some(fixture.path("node_modules/plain/main.js"))
+    );
+
+    let custom = resolver(ResolveOptions {
+        condition_names: strings(&["source"]),
+        main_fields: strings(&["browser", "main"]),
+        ..ResolveOptions::default()
+    });
+    assert_eq!(
+        custom.resolve_source(&importer, "pkg"),
+        Some(fixture.path("node_modules/pkg/src/index.ts"))
+    );
+    assert_eq!(
+        custom.resolve_source(&importer, "plain"),
+        Some(fixture.path("node_modules/plain/browser.js"))
+    );
+}
+
+#[test]
+fn test_module_resolution_read_from_tsconfig() {
+    let fixture = project();
+    fixture.write("tsconfig.json", r#"{ "compilerOptions": { "module": "NodeNext" } }"#);
+    let source = "export * from \"./button.js\";\n";
+    let loader = BarrelLoader::new(BarrelLoaderOptions {
+        resolve_barrel_exports: Some(true),
+        ..Default::default()
+    });
+
+    assert_eq!(
+        loader
+            .process(source, &fixture.source("src/index.ts"))
+            .unwrap(),
+        "export * from \"./button\";\n"
+    );
+}
+
+#[test]
+fn test_explicit_module_resolution_overrides_tsconfig() {
+    let fixture = project();
+    fixture.write("tsconfig.json", r#"{ "compilerOptions": { "moduleResolution": "bundler" } }"#);
+    let source = "export * from \"./button.js\";\n";
+    let process = |module_resolution: &str| {
+        BarrelLoader::new(BarrelLoaderOptions {
+            resolve_barrel_exports: Some(true),
+            module_resolution: Some(module_resolution.to_string()),
+            ..Default::default()
+        })
+        .process(source, &fixture.source("src/index.ts"))
+    };
+
+    assert_eq!(process("node10").unwrap(), source);
+    assert!(process("umd")
+        .unwrap_err()
+        .contains("Unknown moduleResolution"));
+}
@@ -0,0 +1,151 @@
+mod common;
+
+use barrel_loader::{resolve_barrel_napi, BarrelLoader, BarrelLoaderOptions, Resolver};
+use common::Fixture;
+
+fn resolve(fixture: &Fixture, barrel: &str) -> Vec<(String, String, bool)> {
+    Resolver::new()
+        .resolve_barrel(&fixture.path(barrel))
+        .unwrap()
+        .into_iter()
+        .map(|r| (r.export.specifier, r.export.source, r.export.is_type_export))
+        .collect()
+}
+
+#[test]
+fn test_named_export_followed_to_leaf() {
+    let fixture = Fixture::new(&[
+        ("index.ts", "export { Button, Card as Tile } from \"./ui\";\n"),
+        (
+            "ui/index.ts",
+            "export { Button } from \"./button\";\nexport { Card } from \"./card\";\n",
+        ),
+        ("ui/button.tsx", "export const Button = () => null;\n"),
+        ("ui/card.ts", "export const Card = 1;\n"),
+    ]);
+    assert_eq!(
+        resolve(&fixture, "index.ts"),
+        vec![
+            ("Button".to_string(), fixture.source("ui/button.tsx"), false),
+            ("Card as Tile".to_string(), fixture.source("ui/card.ts"), false),
+        ]
+    );
+}
+
+#[test]
+fn test_star_export_of_pure_barrel_is_flattened() {
+    let fixture = Fixture::new(&[
+        ("index.ts", "export * from \"./ui\";\nexport type * from \"./types\";\n"),
+        (
+            "ui/index.ts",
+            "export { Button } from \"./button\";\nexport { default } from \"./app\";\n",
+        ),
+        ("ui/button.ts", "export const Button = 1;\n"),
+        ("ui/app.ts", "export default 1;\n"),
+        ("types/index.ts", "export { User } from \"./user\";\n"),
+        ("types/user.ts", "export interface User {}\n"),
+    ]);
+    assert_eq!(
+        resolve(&fixture, "index.ts"),
+        vec![
+            ("Button".to_string(), fixture.source("ui/button.ts"), false),
+            ("User".to_string(), fixture.source("types/user.ts"), true),
+        ]
+    );
+}
+
+#[test]
+fn test_star_export_of_module_with_declarations_is_kept() {
+    let fixture = Fixture::new(&[
+        ("index.ts", "export * from \"./utils\";\n"),
+        ("utils.ts", "export { a } from \"./a\";\nexport const b = 1;\n"),
+        ("a.ts", "export const a = 1;\n"),
+    ]);
+    assert_eq!(
+        resolve(&fixture, "index.ts"),
+        vec![("*".to_string(), fixture.source("utils.ts"), false)]
+    );
+}
+
+#[test]
+fn test_flattened_stars_keep_star_ambiguity_and_shadowing() {
+    let fixture = Fixture::new(&[
+        (
+            "index.ts",
+            "export * from \"./ui\";\nexport * from \"./b\";\nexport { Icon } from \"./icon\";\n",
+        ),
+        (
+            "ui/index.ts",
+            "export { Button } from \"./button\";\nexport { Icon, Card } from \"./card\";\n",
+        ),
+        ("ui/button.ts", "export const Button = 1;\n"),
+        ("ui/card.ts", "export const Icon = 1;\nexport const Card = 1;\n"),
+        ("b.ts", "export const Button = 2;\nexport const OnlyB = 1;\n"),
+        ("icon.ts", "export const Icon = 2;\n"),
+    ]);
+    let loader =
+        BarrelLoader::new(BarrelLoaderOptions { optimize: Some(true), ..Default::default() });
+    let source = std::fs::read_to_string(fixture.path("index.ts")).unwrap();
+    assert_eq!(
+        loader.process(&source, &fixture.source("index.ts")).unwrap(),
+        "export { OnlyB } from \"./b\";\nexport { Icon } from \"./icon\";\nexport { Card } from \"./ui\";\n"
+    );
+}
+
+#[test]
+fn test_name_two_flattened_stars_provide_is_dropped() {
+    let fixture = Fixture::new(&[
+        ("index.ts", "export * from \"./a\";\nexport * from \"./b\";\n"),
+        ("a/index.ts", "export { Button, Card } from \"./button\";\n"),
+        ("a/button.ts", "export const Button = 1;\nexport const Card = 1;\n"),
+        (
+            "b/index.ts",
+            "export { Button } from \"./button\";\nexport { Card } from \"../a/button\";\n",
+        ),
+        ("b/button.ts", "export const Button = 2;\n"),
+    ]);
+    assert_eq!(
+        resolve(&fixture, "index.ts"),
+        vec![("Card".to_string(), fixture.source("a/button.ts"), false)]
+    );
+}
+
+#[test]
+fn test_unresolved_sources_are_left_untouched() {
+    let fixture = Fixture::new(&[(
+        "index.ts",
+        "export { useState } from \"react\";\nexport { Missing } from \"./missing\";\n",
+    )]);
+    assert_eq!(
+        resolve(&fixture, "index.ts"),
+        vec![
+            ("useState".to_string(), "react".to_string(), false),
+            ("Missing".to_string(), "./missing".to_string(), false),
+        ]
+    );
+}
+
+#[test]
+fn test_cyclic_barrels_terminate() {
+    let fixture = Fixture::new(&[
+        ("index.ts", "export * from \"./a\";\n"),
+        ("a/index.ts", "export * from \"../b\";\nexport { A } from \"./a\";\n"),
+        ("a/a.ts", "export const A = 1;\n"),
+        ("b/index.ts", "export * from \"../a\";\n"),
+    ]);
+    let resolved = resolve(&fixture, "index.ts");
+    assert!(resolved.contains(&("A".to_string(), fixture.source("a/a.ts"), false)));
+}
+
+#[test]
+fn test_resolve_barrel_napi_uses_the_pipeline_resolver() {
+    let fixture = Fixture::new(&[
+        ("tsconfig.json", r#"{ "compilerOptions": { "paths": { "@/*": ["./ui/*"] } } }"#),
+        ("index.ts", "export { Button } from \"@/button\";\n"),
+        ("ui/button.vue", "export const Button = 1;\n"),
+    ]);
+    let options =
+        BarrelLoaderOptions { extensions: Some(vec![".vue".to_string()]), ..Default::default() };
+    let exports = resolve_barrel_napi(fixture.source("index.ts"), Some(options), None).unwrap();
+    assert_eq!(exports[0].source, fixture.source("ui/button.vue"));
+}
@@ -0,0 +1,122 @@
+mod common;
+
+use barrel_loader::{BarrelLoaderOptions, Resolver};
+use common::Fixture;
+use rstest::rstest;
+use std::path::Path;
+
+fn lines(source: &str) -> Vec<u32> {
+    let fixture = Fixture::in_memory(&[("src/module.ts", source)]);
+    Resolver::with_file_system(fixture.file_system())
+        .side_effects(Path::new("/app/src/module.ts"))
+        .lines
+}
+
+#[rstest]
+#[case::declarations(
+    "'use client';\nimport { a } from \"./a\";\nexport const b = 1;\nconst c = {\n  d: () => run(),\n  e(x) {\n    return x();\n  },\n};\nfunction f() {\n  g();\n}\n",
+    &[]
+)]
+#[case::reexports("export * from \"./a\";\nexport { b } from \"./b\";\n", &[])]
+#[case::call("export { a } from \"./a\";\nregister(a);\n", &[2])]
+#[case::css_import("import \"./theme.css\";\nexport { a } from \"./a\";\n", &[1])]
+#[case::global_assignment("window.app = {\n  ready: true,\n};\nglobalThis.x = 1;\n", &[1, 4])]
+#[case::iife("(function () {\n  setup();\n})();\n", &[1])]
+#[case::continued_expression("export const a =\n  b\n    .c;\nconst d = 1,\n  e = 2;\n", &[])]
+#[case::comments("/*\nrun();\n*/\n// run();\nconst a = `\nrun();\n`;\n", &[])]
+#[case::initializer_call("export const registry = register();\n", &[1])]
+#[case::later_declarator("export const a = 1,\n  b = setup();\n", &[1])]
+#[case::initializer_new("const cache = new Map();\n", &[1])]
+#[case::initializer_assignment("let a;\nexport const b = (a = 1);\n", &[2])]
+#[case::tagged_template("export const Title = styled.h1`\n  color: red;\n`;\n", &[1])]
+#[case::default_call("export default setup();\n", &[1])]
+#[case::default_values("export default { a: 1, b: [\"x\"] };\nexport default class A {}\n", &[])]
+#[case::functions(
+    "export const f = (a) => run(a);\nexport const g = function () {\n  run();\n};\nconst h = x => x === 1;\n",
+    &[]
+)]
+#[case::module_exports("module.exports = {};\n", &[1])]
+fn test_top_level_side_effects(#[case] source: &str, #[case] expected: &[u32]) {
+    assert_eq!(lines(source), expected);
+}
+
+#[rstest]
+#[case::flag_false("false", "src/setup.ts", true)]
+#[case::glob_matches_file_name(r#"["*.css", "./src/setup.ts"]"#, "src/setup.ts", false)]
+#[case::glob_misses(r#"["*.css", "./src/polyfills/**"]"#, "src/setup.ts", true)]
+#[case::deep_glob(r#"["./src/**/setup.ts"]"#, "src/setup.ts", false)]
+#[case::flag_true("true", "src/setup.ts", false)]
+fn test_package_side_effects_field(#[case] field: &str, #[case] file: &str, #[case] pure: bool) {
+    let package = format!(r#"{{ "name": "pkg", "sideEffects": {field} }}"#);
+    let fixture = Fixture::in_memory(&[("package.json", &package), (file, "install();\n")]);
+    let verdict =
+        Resolver::with_file_system(fixture.file_system()).side_effects(&fixture.path(file));
+    assert_eq!(verdict.pure, pure);
+}
+
+#[test]
+fn test_package_side_effects_true_marks_pure_modules_impure() {
+    let fixture = Fixture::in_memory(&[
+        ("package.json", r#"{ "name": "pkg", "sideEffects": true }"#),
+        ("src/button.ts", "export const Button = 1;\n"),
+    ]);
+    let verdict = Resolver::with_file_system(fixture.file_system())
+        .side_effects(&fixture.path("src/button.ts"));
+    assert!(!verdict.pure);
+    assert_eq!(verdict.reason, "package.json");
+}
+
+const LAYOUT: &[(&str, &str)] = &[
+    ("src/index.ts",
+            "export { Button, Icon } from \"./ui\";\nexport * from \"./theme\";\n"),
+    ("src/ui/index.ts",
+            "import \"./ui.css\";\nexport { Button } from \"./button\";\nexport { Icon } from \"./icon\";\n"),
+    ("src/ui/button.ts", "export const Button = 1;\n"),
+    ("src/ui/icon.ts", "export const Icon = 1;\n"),
+    ("src/theme/index.ts", "export { useTheme } from \"./hooks\";\n"),
+    ("src/theme/hooks.ts", "export const useTheme = 1;\n"),
+];
+
+#[test]
+fn test_flattening_stops_at_modules_with_side_effects() {
+    let source = "export { Button, Icon } from \"./ui\";\nexport * from \"./theme\";\n";
+    let options = BarrelLoaderOptions { resolve_barrel_exports: Some(true), ..Default::default() };
+    let output = Fixture::in_memory(LAYOUT)
+        .loader(options)
+        .process(source, "/app/src/index.ts")
+        .unwrap();
+
+    assert!(output.contains("from \"./ui\""));
+    assert!(!output.contains("./ui/button"));
+    assert!(output.contains("export { useTheme } from \"./theme/hooks\""));
+}
+
+#[test]
+fn test_rewritten_imports_keep_modules_with_side_effects() {
+    let output = Fixture::in_memory(LAYOUT)
+        .loader(BarrelLoaderOptions::default())
+        .rewrite_imports("import { Button, useTheme } from \"./index\";", "/app/src/app.ts")
+        .unwrap();
+
+    assert_eq!(
+        output,
+        "import { Button } from \"./ui\";\nimport { useTheme } from \"./theme/hooks\";"
+    );
+}
+
+#[test]
+fn test_verdicts_for_every_module_a_barrel_reaches() {
+    let verdicts = Fixture::in_memory(LAYOUT)
+        .loader(BarrelLoaderOptions::default())
+        .analyze_side_effects("/app/src/index.ts")
+        .unwrap();
+    let impure: Vec<(&str, &[u32])> = verdicts
+        .iter()
+        .filter(|verdict| !verdict.pure)
+        .map(|verdict| (verdict.file.as_str(), verdict.lines.as_slice()))
+        .collect();
+
+    assert_eq!(verdicts.len(), 6);
+    assert_eq!(impure, vec![("/app/src/ui/index.ts", &[1][..])]);
+    assert!(verdicts.iter().all(|verdict| verdict.reason == "analysis"));
+}
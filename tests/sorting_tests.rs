@@ -0,0 +1,82 @@
+use barrel_loader::{
+    parse_exports_napi, sort_exports_by_strategy_napi, sort_exports_napi, BarrelLoader,
+    BarrelLoaderOptions,
+};
+use rstest::rstest;
+
+fn sources(source: &str, strategy: &str) -> Vec<String> {
+    let exports = parse_exports_napi(source.to_string()).unwrap();
+    sort_exports_by_strategy_napi(exports, Some(strategy.to_string()), None)
+        .unwrap()
+        .into_iter()
+        .map(|e| e.source)
+        .collect()
+}
+
+#[rstest]
+#[case("alphabetical", vec!["./item10", "./item2", "Zeta", "alpha"])]
+#[case("natural", vec!["./item2", "./item10", "Zeta", "alpha"])]
+#[case("case-insensitive", vec!["./item10", "./item2", "alpha", "Zeta"])]
+#[case("preserve", vec!["./item10", "alpha", "./item2", "Zeta"])]
+#[case("externals-first", vec!["Zeta", "alpha", "./item10", "./item2"])]
+fn test_statement_strategies(#[case] strategy: &str, #[case] expected: Vec<&str>) {
+    let source = r#"export { A } from "./item10";
+export { B } from "alpha";
+export { C } from "./item2";
+export { D } from "Zeta";"#;
+    assert_eq!(sources(source, strategy), expected);
+}
+
+#[test]
+fn test_kind_strategy_groups_statements() {
+    let source = r#"export type { Props } from "./a";
+export { Button } from "./a";
+export { default as App } from "./app";
+export * from "./z";"#;
+    let exports = parse_exports_napi(source.to_string()).unwrap();
+    let sorted = sort_exports_by_strategy_napi(exports, Some("kind".to_string()), None).unwrap();
+    let kinds: Vec<(&str, bool)> = sorted
+        .iter()
+        .map(|e| (e.export_type.as_str(), e.is_type_export))
+        .collect();
+    assert_eq!(
+        kinds,
+        vec![
+            ("namespace", false),
+            ("named", false),
+            ("named", false),
+            ("named", true)
+        ]
+    );
+}
+
+#[test]
+fn test_specifiers_sorted_within_preserved_statements() {
+    let loader = BarrelLoader::new(BarrelLoaderOptions {
+        sort_strategy: Some("preserve".to_string()),
+        specifier_sort_strategy: Some("natural".to_string()),
+        ..Default::default()
+    });
+    let source = r#"export { Item10, Item2 } from "./z";
+export { B, A } from "./a";"#;
+    let result = loader.process(source, "/path/to/index.ts").unwrap();
+    assert_eq!(
+        result,
+        "export { Item2, Item10 } from \"./z\";\nexport { A, B } from \"./a\";\n"
+    );
+}
+
+#[test]
+fn test_default_sort_is_byte_wise() {
+    let exports = parse_exports_napi(r#"export { b, a, Z } from "./x";"#.to_string()).unwrap();
+    let specifiers: Vec<String> = sort_exports_napi(exports)
+        .into_iter()
+        .map(|e| e.specifier)
+        .collect();
+    assert_eq!(specifiers, vec!["Z", "a", "b"]);
+}
+
+#[test]
+fn test_unknown_strategy_is_rejected() {
+    assert!(sort_exports_by_strategy_napi(Vec::new(), Some("random".to_string()), None).is_err());
+}
@@ -0,0 +1,155 @@
+mod common;
+
+use barrel_loader::{BarrelLoader, BarrelLoaderOptions, Resolver, SourceStyle};
+use common::Fixture;
+use rstest::rstest;
+
+/// Three levels of barrels between `src/index.ts` and the leaf modules
+fn deep_tree() -> Fixture {
+    Fixture::new(&[
+        (
+            "tsconfig.json",
+            r#"{ "compilerOptions": { "baseUrl": ".", "paths": { "@ui/*": ["src/ui/*"] } } }"#,
+        ),
+        ("src/index.ts", "export * from \"./ui\";\nexport { format } from \"./utils\";\n"),
+        (
+            "src/ui/index.ts",
+            "export * from \"./forms\";\nexport { Card } from \"./card/index\";\n",
+        ),
+        ("src/ui/forms/index.ts", "export { Button } from \"./button\";\n"),
+        ("src/ui/forms/button.tsx", "export const Button = 1;\n"),
+        ("src/ui/card/index.ts", "export const Card = 1;\n"),
+        ("src/utils/index.ts", "export { format } from \"../../lib/format\";\n"),
+        ("lib/format.ts", "export const format = 1;\n"),
+    ])
+}
+
+fn process(fixture: &Fixture, source_style: &str) -> String {
+    let source = std::fs::read_to_string(fixture.path("src/index.ts")).unwrap();
+    BarrelLoader::new(BarrelLoaderOptions {
+        resolve_barrel_exports: Some(true),
+        source_style: Some(source_style.to_string()),
+        ..Default::default()
+    })
+    .process(&source, &fixture.source("src/index.ts"))
+    .unwrap()
+}
+
+#[rstest]
+#[case("relative", &["./ui/forms/button", "./ui/card", "../lib/format"])]
+#[case("extensionless", &["./ui/forms/button", "./ui/card/index", "../lib/format"])]
+#[case("extension", &["./ui/forms/button.tsx", "./ui/card/index.ts", "../lib/format.ts"])]
+#[case("alias", &["@ui/forms/button", "@ui/card", "lib/format"])]
+#[case("fully-specified", &["./ui/forms/button.js", "./ui/card/index.js", "../lib/format.js"])]
+fn test_deep_tree_sources_written_from_root_barrel(
+    #[case] source_style: &str,
+    #[case] sources: &[&str],
+) {
+    let fixture = deep_tree();
+    assert_eq!(
+        process(&fixture, source_style),
+        format!(
+            "export {{ Button }} from \"{}\";\nexport {{ Card }} from \"{}\";\n\
+             export {{ format }} from \"{}\";\n",
+            sources[0], sources[1], sources[2]
+        )
+    );
+}
+
+#[test]
+fn test_absolute_style_keeps_resolved_paths() {
+    let fixture = deep_tree();
+    assert!(process(&fixture, "absolute").contains(&fixture.source("src/ui/forms/button.tsx")));
+}
+
+#[test]
+fn test_unknown_source_style_rejected() {
+    let result = BarrelLoader::new(BarrelLoaderOptions {
+        source_style: Some("shortest".to_string()),
+        ..Default::default()
+    })
+    .process("export { a } from \"./a\";\n", "/tmp/index.ts");
+    assert!(result.unwrap_err().contains("Unknown sourceStyle"));
+}
+
+#[test]
+fn test_shortened_specifier_must_resolve_back() {
+    // `./button` would pick `button.ts` first, so the `.tsx` leaf keeps its extension
+    let fixture = Fixture::new(&[
+        ("index.ts", ""),
+        ("button.ts", "export const Other = 1;\n"),
+        ("button.tsx", "export const Button = 1;\n"),
+    ]);
+    let resolver = Resolver::new();
+    let importer = fixture.path("index.ts");
+
+    assert_eq!(
+        resolver.source_specifier(&importer, &fixture.path("button.tsx"), SourceStyle::Relative),
+        "./button.tsx"
+    );
+    assert_eq!(
+        resolver.source_specifier(&importer, &fixture.path("button.ts"), SourceStyle::Relative),
+        "./button"
+    );
+}
+
+#[test]
+fn test_fully_specified_without_flattening() {
+    let fixture = Fixture::new(&[
+        ("src/index.ts", ""),
+        ("src/ui/index.ts", "export const Button = 1;\n"),
+        ("src/node.mts", "export const node = 1;\n"),
+        ("src/legacy.cjs", "exports.legacy = 1;\n"),
+    ]);
+    let source = "export * from \"./ui\";\nexport { node } from \"./node\";\n\
+                  export { legacy } from \"./legacy.cjs\";\n\
+                  export { h } from \"preact\";\nexport { gone } from \"./missing\";\n";
+    let result = BarrelLoader::new(BarrelLoaderOptions {
+        source_style: Some("fully-specified".to_string()),
+        ..Default::default()
+    })
+    .process(source, &fixture.source("src/index.ts"))
+    .unwrap();
+
+    assert_eq!(
+        result,
+        "export * from \"./ui/index.js\";\nexport { node } from \"./node.mjs\";\n\
+         export { legacy } from \"./legacy.cjs\";\n\
+         export { h } from \"preact\";\nexport { gone } from \"./missing\";\n"
+    );
+}
+
+#[test]
+fn test_barrel_sources_resolved_and_rebased_in_one_call() {
+    let fixture = deep_tree();
+    let loader = BarrelLoader::new(BarrelLoaderOptions {
+        source_style: Some("alias".to_string()),
+        ..Default::default()
+    });
+    let sources = vec![
+        "./forms".to_string(),
+        "./card/index".to_string(),
+        "./gone".to_string(),
+    ];
+
+    assert_eq!(
+        loader
+            .resolve_sources(&fixture.source("src/ui/index.ts"), &sources)
+            .unwrap(),
+        vec![
+            Some(fixture.path("src/ui/forms/index.ts")),
+            Some(fixture.path("src/ui/card/index.ts")),
+            None
+        ]
+    );
+    assert_eq!(
+        loader
+            .rebase_sources(
+                &fixture.source("src/ui/index.ts"),
+                &fixture.source("src/index.ts"),
+                &sources
+            )
+            .unwrap(),
+        vec!["@ui/forms", "@ui/card", "./gone"]
+    );
+}
@@ -0,0 +1,65 @@
+mod common;
+
+use barrel_loader::Resolver;
+use common::Fixture;
+use rstest::rstest;
+
+fn package() -> Fixture {
+    Fixture::new(&[
+        (
+            "package.json",
+            r##"{
+  "name": "app",
+  "imports": {
+    "#internal/*": { "node": "./src/node/*.ts", "import": "./src/internal/*.ts" },
+    "#internal/legacy/*": "./src/legacy/*.ts",
+    "#config": [{ "require": "./src/config.cjs" }, "./src/config.ts"],
+    "#dep": "dep"
+  }
+}"##,
+        ),
+        ("src/internal/hooks.ts", "export const useThing = 1;\n"),
+        ("src/legacy/old.ts", "export const old = 1;\n"),
+        ("src/config.ts", "export const config = 1;\n"),
+        ("src/index.ts", "export * from \"#internal/hooks\";\n"),
+        ("node_modules/dep/package.json", r#"{ "name": "dep", "main": "./index.js" }"#),
+        ("node_modules/dep/index.js", "export const dep = 1;\n"),
+    ])
+}
+
+#[rstest]
+#[case("#internal/hooks", Some("src/internal/hooks.ts"))]
+#[case("#internal/legacy/old", Some("src/legacy/old.ts"))]
+#[case("#config", Some("src/config.ts"))]
+#[case("#missing", None)]
+fn test_resolve_subpath_imports(#[case] specifier: &str, #[case] expected: Option<&str>) {
+    let fixture = package();
+    let importer = fixture.path("src/index.ts");
+    assert_eq!(
+        Resolver::new().resolve_source(&importer, specifier),
+        expected.map(|path| fixture.path(path))
+    );
+}
+
+#[test]
+fn test_package_targets_require_package_resolution() {
+    let fixture = package();
+    let importer = fixture.path("src/index.ts");
+    assert_eq!(Resolver::new().resolve_source(&importer, "#dep"), None);
+    assert_eq!(
+        Resolver::new()
+            .with_packages(true)
+            .resolve_source(&importer, "#dep"),
+        Some(fixture.path("node_modules/dep/index.js"))
+    );
+}
+
+#[test]
+fn test_barrel_flattened_through_subpath_import() {
+    let fixture = package();
+    let resolved = Resolver::new()
+        .resolve_barrel(&fixture.path("src/index.ts"))
+        .unwrap();
+    assert_eq!(resolved[0].export.source, fixture.source("src/internal/hooks.ts"));
+    assert_eq!(resolved[0].export.specifier, "*");
+}
@@ -0,0 +1,74 @@
+mod common;
+
+use barrel_loader::{BarrelLoaderOptions, Resolver};
+use common::Fixture;
+use std::path::{Path, PathBuf};
+
+const STORE: &str = "/app/node_modules/.pnpm/@acme+ui@1.0.0/node_modules/@acme/ui";
+
+fn pnpm_project() -> Fixture {
+    Fixture::in_memory(&[(
+        "src/index.ts",
+        "export * from \"@acme/ui\";\nexport { Button } from \"@acme/ui/button\";\n",
+    )])
+    .with_file(&format!("{STORE}/package.json"), r#"{ "module": "./index.js" }"#)
+    .with_file(&format!("{STORE}/index.js"), "export { Button } from \"./button.js\";\n")
+    .with_file(&format!("{STORE}/button.js"), "export const Button = 1;\n")
+    .with_symlink("node_modules/@acme/ui", "../.pnpm/@acme+ui@1.0.0/node_modules/@acme/ui")
+}
+
+fn process(symlinks: Option<bool>) -> String {
+    pnpm_project()
+        .loader(BarrelLoaderOptions {
+            resolve_barrel_exports: Some(true),
+            resolve_packages: Some(true),
+            symlinks,
+            ..Default::default()
+        })
+        .process(
+            "export * from \"@acme/ui\";\nexport { Button } from \"@acme/ui/button\";\n",
+            "/app/src/index.ts",
+        )
+        .unwrap()
+}
+
+#[test]
+fn test_resolved_paths_are_canonical_by_default() {
+    let fixture = pnpm_project();
+    let resolver = Resolver::with_file_system(fixture.file_system()).with_packages(true);
+
+    assert_eq!(
+        resolver.resolve_source(Path::new("/app/src/index.ts"), "@acme/ui"),
+        Some(PathBuf::from(format!("{STORE}/index.js")))
+    );
+}
+
+#[test]
+fn test_symlinked_paths_kept_when_disabled() {
+    let fixture = pnpm_project();
+    let resolver = Resolver::with_file_system(fixture.file_system())
+        .with_packages(true)
+        .with_symlinks(false);
+
+    assert_eq!(
+        resolver.resolve_source(Path::new("/app/src/index.ts"), "@acme/ui"),
+        Some(PathBuf::from("/app/node_modules/@acme/ui/index.js"))
+    );
+    assert_eq!(
+        resolver.canonical(Path::new("/app/node_modules/@acme/ui/index.js")),
+        PathBuf::from(format!("{STORE}/index.js"))
+    );
+}
+
+#[test]
+fn test_same_module_through_link_and_store_deduplicated() {
+    assert_eq!(process(None), "export { Button } from \"../node_modules/.pnpm/@acme+ui@1.0.0/node_modules/@acme/ui/button\";\n");
+}
+
+#[test]
+fn test_symlinks_false_dedupes_on_real_path_but_outputs_link() {
+    assert_eq!(
+        process(Some(false)),
+        "export { Button } from \"../node_modules/@acme/ui/button\";\n"
+    );
+}
@@ -0,0 +1,110 @@
+mod common;
+
+use barrel_loader::{BarrelLoader, BarrelLoaderOptions, OsFileSystem, Resolver, TsConfig};
+use common::Fixture;
+
+#[test]
+fn test_load_jsonc_with_extends_chain() {
+    let fixture = Fixture::new(&[
+        (
+            "tsconfig.json",
+            r#"{
+  // project config
+  "extends": "./configs/base.json",
+  "compilerOptions": {
+    "baseUrl": "./src", /* overrides base */
+  },
+}"#,
+        ),
+        (
+            "configs/base.json",
+            r#"{ "compilerOptions": { "baseUrl": "..", "paths": { "@/*": ["./src/*",], "http://x": ["./y"] } } }"#,
+        ),
+    ]);
+    let config = TsConfig::load(&OsFileSystem, &fixture.path("tsconfig.json")).unwrap();
+    assert_eq!(config.base_url, Some(fixture.path("src")));
+    assert_eq!(config.paths_dir, Some(fixture.path("configs")));
+    assert_eq!(config.paths.len(), 2);
+}
+
+#[test]
+fn test_extends_package_from_node_modules() {
+    let fixture = Fixture::new(&[
+        ("app/tsconfig.json", r#"{ "extends": "@acme/tsconfig/base" }"#),
+        (
+            "node_modules/@acme/tsconfig/base.json",
+            r#"{ "compilerOptions": { "paths": { "~ui/*": ["./ui/*"] } } }"#,
+        ),
+    ]);
+    let config = TsConfig::load(&OsFileSystem, &fixture.path("app/tsconfig.json")).unwrap();
+    assert_eq!(config.paths_dir, Some(fixture.path("node_modules/@acme/tsconfig")));
+}
+
+#[test]
+fn test_longest_prefix_pattern_wins() {
+    let config = TsConfig {
+        base_url: Some("/repo".into()),
+        paths: vec![
+            ("@/*".to_string(), vec!["src/*".to_string()]),
+            ("@/components/*".to_string(), vec!["lib/ui/*".to_string()]),
+        ],
+        paths_dir: None,
+        module_resolution: None,
+    };
+    assert_eq!(
+        config.resolve_alias("@/components/button"),
+        vec![
+            std::path::PathBuf::from("/repo/lib/ui/button"),
+            std::path::PathBuf::from("/repo/@/components/button"),
+        ]
+    );
+}
+
+#[test]
+fn test_resolver_follows_aliased_sources() {
+    let fixture = Fixture::new(&[
+        (
+            "tsconfig.json",
+            r#"{ "compilerOptions": { "paths": { "@/*": ["./src/*"], "~ui/*": ["./src/ui/*"] } } }"#,
+        ),
+        ("src/index.ts", "export * from \"@/components\";\n"),
+        ("src/components/index.ts", "export { Button } from \"~ui/button\";\n"),
+        ("src/ui/button.tsx", "export const Button = 1;\n"),
+    ]);
+    let tsconfig = TsConfig::load(&OsFileSystem, &fixture.path("tsconfig.json")).unwrap();
+    let resolved = Resolver::new()
+        .with_tsconfig(tsconfig)
+        .resolve_barrel(&fixture.path("src/index.ts"))
+        .unwrap();
+    assert_eq!(resolved.len(), 1);
+    assert_eq!(resolved[0].export.source, fixture.source("src/ui/button.tsx"));
+}
+
+#[test]
+fn test_processor_discovers_nearest_tsconfig() {
+    let fixture = Fixture::new(&[
+        ("tsconfig.json", r#"{ "compilerOptions": { "baseUrl": "." } }"#),
+        ("src/index.ts", "export { Input } from \"src/input\";\n"),
+        ("src/input.ts", "export const Input = 1;\n"),
+    ]);
+    let loader = BarrelLoader::new(BarrelLoaderOptions {
+        resolve_barrel_exports: Some(true),
+        ..Default::default()
+    });
+    let source = std::fs::read_to_string(fixture.path("src/index.ts")).unwrap();
+    let result = loader
+        .process(&source, &fixture.source("src/index.ts"))
+        .unwrap();
+    assert_eq!(result, "export { Input } from \"./input\";\n");
+}
+
+#[test]
+fn test_explicit_tsconfig_must_exist() {
+    let loader = BarrelLoader::new(BarrelLoaderOptions {
+        resolve_barrel_exports: Some(true),
+        tsconfig: Some("/does/not/exist/tsconfig.json".to_string()),
+        ..Default::default()
+    });
+    let result = loader.process("export { A } from \"./a\";\n", "/tmp/index.ts");
+    assert!(result.is_err());
+}